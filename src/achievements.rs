@@ -0,0 +1,109 @@
+// Achievements unlocked by the games of the state file
+//
+// Every achievement is a condition on the saved `Games` records, so the list can always be
+// computed again from an older state file. With --achievements the ids are also stored in
+// the state file, in the order they were earned, and announced when a game unlocks one.
+
+use crate::interact_model::GUESS_CHANCE;
+use crate::json_parser::{Gamejson, Games};
+use crate::utils;
+
+pub struct Achievement {
+	pub id: &'static str,
+	pub name: &'static str,
+	pub description: &'static str,
+	earned: fn(&[Games]) -> bool,
+}
+
+pub const ACHIEVEMENTS: [Achievement; 9] = [
+	Achievement { id: "first_win", name: "First win", description: "Win a game", earned: first_win },
+	Achievement { id: "hole_in_one", name: "Hole in one", description: "Solve a puzzle with the first guess", earned: hole_in_one },
+	Achievement { id: "two_guesses", name: "Sharp", description: "Solve a puzzle in two guesses", earned: two_guesses },
+	Achievement { id: "close_call", name: "Close call", description: "Solve a puzzle with the last guess", earned: close_call },
+	Achievement { id: "hard_win", name: "No shortcuts", description: "Win a game in difficult mode", earned: hard_win },
+	Achievement { id: "double_letter", name: "Seeing double", description: "Solve an answer with a repeated letter", earned: double_letter },
+	Achievement { id: "streak_10", name: "On a roll", description: "Win 10 games in a row", earned: streak_10 },
+	Achievement { id: "games_10", name: "Regular", description: "Play 10 games", earned: |games| games.len() >= 10 },
+	Achievement { id: "games_100", name: "Devoted", description: "Play 100 games", earned: |games| games.len() >= 100 },
+];
+
+fn won(games: &[Games]) -> impl Iterator<Item = &Games> {
+	games.iter().filter(|game| game.check_result())
+}
+
+fn first_win(games: &[Games]) -> bool {
+	won(games).next().is_some()
+}
+
+fn hole_in_one(games: &[Games]) -> bool {
+	won(games).any(|game| game.guesses_number() == 1)
+}
+
+fn two_guesses(games: &[Games]) -> bool {
+	won(games).any(|game| game.guesses_number() == 2)
+}
+
+fn close_call(games: &[Games]) -> bool {
+	won(games).any(|game| game.guesses_number() == GUESS_CHANCE)
+}
+
+fn hard_win(games: &[Games]) -> bool {
+	won(games).any(|game| game.difficult())
+}
+
+fn double_letter(games: &[Games]) -> bool {
+	won(games).any(|game| {
+		let mut letters = utils::split_letters(game.answer());
+		let count = letters.len();
+		letters.sort();
+		letters.dedup();
+		letters.len() < count
+	})
+}
+
+fn streak_10(games: &[Games]) -> bool {
+	let mut streak = 0;
+	for game in games {
+		streak = if game.check_result() { streak + 1 } else { 0 };
+		if streak >= 10 {
+			return true;
+		}
+	}
+	false
+}
+
+/// Every achievement the games earn
+pub fn earned(games: &[Games]) -> Vec<&'static Achievement> {
+	ACHIEVEMENTS.iter().filter(|achievement| (achievement.earned)(games)).collect()
+}
+
+/// Store the achievements earned since the last call and return them
+pub fn unlock(gamejson: &mut Gamejson) -> Vec<&'static Achievement> {
+	let new: Vec<&'static Achievement> = earned(gamejson.games()).into_iter()
+		.filter(|achievement| !gamejson.achievements().iter().any(|id| id == achievement.id))
+		.collect();
+	for achievement in &new {
+		gamejson.add_achievement(achievement.id);
+	}
+	new
+}
+
+pub fn announce(achievements: &[&Achievement]) {
+	for achievement in achievements {
+		println!("{} {}: {}", console::style("Achievement unlocked!").bold().yellow(), achievement.name, achievement.description);
+	}
+}
+
+/// `wordle stats achievements`: every achievement and whether the saved games earned it
+pub fn print_achievements(gamejson: &Gamejson, is_tty: bool) {
+	let earned = earned(gamejson.games());
+	for achievement in &ACHIEVEMENTS {
+		let unlocked = earned.iter().any(|earned| earned.id == achievement.id);
+		if is_tty {
+			println!("[{}] {}: {}", if unlocked { "x" } else { " " }, achievement.name, achievement.description);
+		}
+		else{
+			println!("{} {}", achievement.id, if unlocked { "UNLOCKED" } else { "LOCKED" });
+		}
+	}
+}
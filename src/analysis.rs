@@ -0,0 +1,92 @@
+// Skill and luck of every guess of a game
+//
+// For each guess: the possible answers before and after it, the information it was
+// expected to give (entropy of its feedback over the possible answers), the same for the
+// best guess the solver finds, and the information it actually gave. Luck is actual minus
+// expected: positive when the feedback cut the answers down more than usual.
+
+use clap::Args;
+use crate::json_parser::Gamejson;
+use crate::replay::{self, GameSelector};
+use crate::solver::Solver;
+use crate::utils::WordDict;
+
+#[derive(Args, Debug, Clone)]
+pub struct AnalyzeArgs {
+	#[command(flatten)]
+	game: GameSelector,
+}
+
+pub struct GuessAnalysis {
+	pub guess: String,
+	pub before: usize,
+	pub after: usize,
+	/// Expected bits of the guess
+	pub expected: f64,
+	/// Expected bits of `best_guess`
+	pub best_expected: f64,
+	/// The solver's pick, or the guess itself when it is expected to give more
+	pub best_guess: String,
+	/// Bits actually gained
+	pub actual: f64,
+}
+
+impl GuessAnalysis {
+	pub fn luck(&self) -> f64 {
+		self.actual - self.expected
+	}
+}
+
+pub fn analyze(guess_history: &[(String, String)], word_dict: &WordDict) -> Vec<GuessAnalysis> {
+	let mut solver = Solver::new(word_dict);
+	let mut result = Vec::new();
+	for (guess, pattern) in guess_history {
+		let before = solver.candidate_count();
+		let expected = solver.entropy(guess);
+		// the solver only weighs part of the word list, the player's guess can do better
+		let solver_guess = solver.best_guess().unwrap_or_default();
+		let solver_expected = solver.entropy(&solver_guess);
+		let (best_guess, best_expected) = match expected > solver_expected {
+			true => (guess.clone(), expected),
+			false => (solver_guess, solver_expected),
+		};
+		solver.apply(guess, pattern);
+		let after = solver.candidate_count();
+		let actual = match (before, after) {
+			(0, _) | (_, 0) => 0.0,
+			_ => (before as f64 / after as f64).log2(),
+		};
+		result.push(GuessAnalysis { guess: guess.clone(), before, after, expected, best_expected, best_guess, actual });
+	}
+	result
+}
+
+pub fn print_analysis(analysis: &[GuessAnalysis], is_tty: bool) {
+	if is_tty {
+		println!("Guess  Answers left   Expected bits (best)     Actual  Luck");
+		for row in analysis {
+			println!("{}  {:>5} -> {:<5}  {:>5.2} ({:.2} {})  {:>6.2}  {:+.2}", row.guess, row.before, row.after,
+				row.expected, row.best_expected, row.best_guess, row.actual, row.luck());
+		}
+	}
+	else{
+		for row in analysis {
+			println!("{} {} {} {:.2} {:.2} {} {:.2} {:+.2}", row.guess, row.before, row.after,
+				row.expected, row.best_expected, row.best_guess, row.actual, row.luck());
+		}
+	}
+}
+
+/// `wordle analyze`: the breakdown for a game of the state file
+pub fn run(analyze_args: AnalyzeArgs, gamejson: &Gamejson, word_dict: &WordDict, is_tty: bool) -> Result<(), Box<dyn std::error::Error>> {
+	let (number, game) = replay::select(&analyze_args.game, gamejson, word_dict)?;
+	let gameinfo = replay::rescore(number, game, word_dict)?;
+	if is_tty {
+		println!("Game #{}, answer {}", number, gameinfo.answer());
+	}
+	else{
+		println!("GAME {} {}", number, gameinfo.answer());
+	}
+	print_analysis(&analyze(gameinfo.guess_history(), word_dict), is_tty);
+	Ok(())
+}
@@ -0,0 +1,191 @@
+// `wordle arena`: external solver programs play the same answers, scored by `GameInfo`
+//
+// Every solver is started once and plays all games over its stdin/stdout, one message
+// per line:
+//   referee -> solver   "START <letters> <chances>" when a game begins, then after each
+//                       guess one of: the colors ("GYRRR"), "INVALID", "CORRECT n" or
+//                       "FAILED <answer>" once the game is over; "END" after the last game
+//   solver -> referee   one guess per line
+//
+// A game is lost when the solver uses up --guess-limit guesses, invalid ones included.
+// A solver that takes longer than --time-limit for a guess or exits is disqualified and
+// loses its remaining games.
+
+use std::io::{BufRead, BufReader, Write};
+use std::process::{Child, ChildStdin, Command, Stdio};
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError};
+use std::thread;
+use std::time::{Duration, Instant};
+use clap::Args;
+use crate::interact_model::{GameInfo, GUESS_CHANCE};
+use crate::shuffle::ShuffleVersion;
+use crate::utils::WordDict;
+
+#[derive(Args, Debug, Clone)]
+pub struct ArenaArgs {
+	/// Solver commands, program and arguments separated by spaces
+	#[arg(required = true)]
+	solvers: Vec<String>,
+
+	/// Number of answers every solver plays, taken from --seed starting at --day
+	#[arg(long, default_value_t = 10)]
+	games: usize,
+
+	/// Milliseconds a solver may take for one guess
+	#[arg(long, default_value_t = 5000)]
+	time_limit: u64,
+
+	/// Guesses a solver may submit per game, invalid ones included
+	#[arg(long, default_value_t = 10)]
+	guess_limit: usize,
+}
+
+struct Contestant {
+	name: String,
+	child: Child,
+	stdin: ChildStdin,
+	lines: Receiver<String>,
+	solved: usize,
+	/// Guesses used in the solved games
+	guesses: usize,
+	time: Duration,
+	disqualified: Option<String>,
+}
+
+pub fn run(arena_args: ArenaArgs, word_dict: &WordDict, seed: u64, day: usize, shuffle_version: ShuffleVersion, is_difficult: bool, is_tty: bool) -> Result<(), Box<dyn std::error::Error>> {
+	let answers = (day..day + arena_args.games)
+		.map(|day| word_dict.day_answer(seed, day, shuffle_version))
+		.collect::<Result<Vec<String>, _>>()?;
+	let time_limit = Duration::from_millis(arena_args.time_limit);
+
+	let mut contestants = arena_args.solvers.iter()
+		.map(|command| Contestant::spawn(command))
+		.collect::<Result<Vec<Contestant>, _>>()?;
+	for contestant in contestants.iter_mut() {
+		for answer in &answers {
+			if contestant.disqualified.is_some() {
+				break;
+			}
+			match contestant.play(answer, is_difficult, word_dict, time_limit, arena_args.guess_limit) {
+				Ok(Some(guesses)) => {
+					contestant.solved += 1;
+					contestant.guesses += guesses;
+				},
+				Ok(None) => (),
+				Err(reason) => contestant.disqualified = Some(reason),
+			}
+		}
+		let _ = writeln!(contestant.stdin, "END");
+		let _ = contestant.child.kill();
+		let _ = contestant.child.wait();
+		if is_tty {
+			println!("{}: solved {} of {}", contestant.name, contestant.solved, answers.len());
+		}
+	}
+
+	contestants.sort_by(|a, b| b.solved.cmp(&a.solved)
+		.then(a.average().total_cmp(&b.average()))
+		.then(a.time.cmp(&b.time)));
+	print_leaderboard(&contestants, answers.len(), is_tty);
+	Ok(())
+}
+
+impl Contestant {
+	fn spawn(command: &str) -> Result<Contestant, Box<dyn std::error::Error>> {
+		let mut parts = command.split_whitespace();
+		let program = parts.next().ok_or("empty solver command")?;
+		let mut child = Command::new(program)
+			.args(parts)
+			.stdin(Stdio::piped())
+			.stdout(Stdio::piped())
+			.stderr(Stdio::null())
+			.spawn()
+			.map_err(|err| format!("cannot start solver {}: {}", command, err))?;
+		let stdin = child.stdin.take().unwrap();
+		let stdout = child.stdout.take().unwrap();
+		// read on a separate thread so that a silent solver can be timed out
+		let (sender, lines) = mpsc::channel();
+		thread::spawn(move || {
+			for line in BufReader::new(stdout).lines().map_while(Result::ok) {
+				if sender.send(line).is_err() {
+					break;
+				}
+			}
+		});
+		Ok(Contestant { name: String::from(command), child, stdin, lines, solved: 0, guesses: 0, time: Duration::ZERO, disqualified: None })
+	}
+
+	/// Some(guesses) if solved, None if lost, Err(reason) if the solver broke the rules
+	fn play(&mut self, answer: &str, is_difficult: bool, word_dict: &WordDict, time_limit: Duration, guess_limit: usize) -> Result<Option<usize>, String> {
+		let mut gameinfo = GameInfo::new(answer, is_difficult, word_dict);
+		self.send(&format!("START {} {}", word_dict.length, GUESS_CHANCE))?;
+		for _ in 0..guess_limit {
+			let start = Instant::now();
+			let guess = self.lines.recv_timeout(time_limit);
+			self.time += start.elapsed();
+			let guess = match guess {
+				Ok(guess) => guess,
+				Err(RecvTimeoutError::Timeout) => return Err(String::from("timed out")),
+				Err(RecvTimeoutError::Disconnected) => return Err(String::from("exited")),
+			};
+			if gameinfo.make_guess(&word_dict.alphabet.normalize(&guess), word_dict).is_err() {
+				self.send("INVALID")?;
+				continue;
+			}
+			match gameinfo.result_line() {
+				Some(result) => {
+					self.send(&result)?;
+					return Ok(gameinfo.result().filter(|solved| *solved).map(|_| gameinfo.guess_history().len()));
+				},
+				None => {
+					let (_, pattern) = gameinfo.guess_history().last().unwrap();
+					self.send(pattern)?;
+				},
+			}
+		}
+		self.send(&format!("FAILED {}", answer))?;
+		Ok(None)
+	}
+
+	fn send(&mut self, line: &str) -> Result<(), String> {
+		writeln!(self.stdin, "{}", line).map_err(|_| String::from("exited"))
+	}
+
+	/// Average guesses in solved games, infinite if none was solved
+	fn average(&self) -> f64 {
+		match self.solved {
+			0 => f64::INFINITY,
+			_ => self.guesses as f64 / self.solved as f64,
+		}
+	}
+}
+
+fn print_leaderboard(contestants: &[Contestant], games: usize, is_tty: bool) {
+	if is_tty {
+		println!("Leaderboard:");
+	}
+	else{
+		println!("LEADERBOARD");
+	}
+	for (rank, contestant) in contestants.iter().enumerate() {
+		let average = match contestant.solved {
+			0 => 0.0,
+			_ => contestant.average(),
+		};
+		if is_tty {
+			print!("{}. {}: solved {} of {}, {:.2} guesses on average, {} ms", rank + 1, contestant.name,
+				contestant.solved, games, average, contestant.time.as_millis());
+			match &contestant.disqualified {
+				Some(reason) => println!(", disqualified ({})", reason),
+				None => println!(),
+			}
+		}
+		else{
+			print!("{} {} {} {} {:.2}", rank + 1, contestant.name, contestant.solved, games - contestant.solved, average);
+			match &contestant.disqualified {
+				Some(reason) => println!(" DISQUALIFIED {}", reason),
+				None => println!(),
+			}
+		}
+	}
+}
@@ -1532,3 +1532,123 @@ pub const ACCEPTABLE: &[&str] = &[
     "zowee", "zowie", "zulus", "zupan", "zupas", "zuppa", "zurfs", "zuzim", "zygal", "zygon",
     "zymes", "zymic",
 ];
+
+pub const GERMAN_FINAL: &[&str] = &[
+    "abend", "achse", "adler", "agent", "aktie", "alarm", "alter", "ampel", "angel", "angst",
+    "apfel", "arena", "atlas", "backe", "bauch", "beere", "besen", "beton", "bibel", "biene",
+    "birne", "blatt", "blick", "blume", "boden", "bogen", "bohne", "braut", "brief", "buche",
+    "dachs", "dampf", "datum", "decke", "deich", "degen", "draht", "ebene", "eimer", "eisen",
+    "engel", "enkel", "erbse", "ernte", "essig", "fabel", "faden", "fahne", "falke", "farbe",
+    "feder", "feige", "feier", "felge", "ferne", "feuer", "fibel", "fisch", "fluch", "floss",
+    "fluss", "folge", "frage", "frist", "frost", "fuchs", "funke", "gabel", "geist", "gerte",
+    "gicht", "glanz", "gleis", "gnade", "griff", "grube", "gunst", "hafen", "hagel", "halle",
+    "handy", "hecke", "heide", "herde", "honig", "insel", "jacke", "jubel", "kabel", "kamin",
+    "kanal", "kanne", "kante", "karte", "katze", "kegel", "kehle", "kelle", "kerze", "kette",
+    "kiste", "klage", "klang", "kleid", "klima", "knabe", "knopf", "kohle", "kraft", "kranz",
+    "kreis", "krieg", "kugel", "kurve", "lager", "lampe", "lanze", "laser", "leben", "leder",
+    "lehre", "leine", "licht", "liebe", "linie", "liste", "lunge", "macht", "magen", "maler",
+    "mango", "markt", "maske", "mauer", "meile", "menge", "messe", "meter", "miete", "minze",
+    "monat", "motor", "motte", "musik", "nacht", "nadel", "nagel", "narbe", "nebel", "nonne",
+    "nudel", "onkel", "opfer", "orgel", "paket", "palme", "panne", "pause", "perle", "pferd",
+    "pflug", "pizza", "platz", "preis", "prinz", "probe", "pudel", "puppe", "quark", "radio",
+    "rasen", "ratte", "rauch", "regel", "regen", "reise", "rinde", "robbe", "roman", "ruder",
+    "ruine", "sache", "saite", "salat", "salbe", "samen", "schaf", "schal", "schuh", "seele",
+    "segel", "seife", "seite", "senke", "socke", "sonne", "sorge", "spatz", "spiel", "sport",
+    "staat", "stadt", "stahl", "stall", "stamm", "stein", "stern", "stier", "stirn", "stoff",
+    "strom", "stuhl", "sturm", "suche", "suppe", "tafel", "tanne", "tante", "tasse", "taube",
+    "tinte", "tisch", "tonne", "torte", "traum", "treue", "truhe", "tulpe", "vater", "vogel",
+    "waage", "wache", "waffe", "wagen", "wange", "wanne", "weide", "welle", "wespe", "wiese",
+    "wolke", "wolle", "wunde", "wurst", "zange", "zebra", "zeile", "zelle", "ziege", "zunge",
+    "zwerg",
+];
+
+pub const GERMAN_ACCEPTABLE: &[&str] = &[
+    "abbau", "abend", "achse", "acker", "adler", "affen", "agent", "akten", "aktie", "alarm",
+    "alpen", "altar", "alter", "ampel", "angel", "angst", "anker", "apfel", "arena", "armee",
+    "atlas", "backe", "bande", "basis", "bauch", "bauen", "bauer", "beere", "besen", "beton",
+    "beute", "bibel", "biene", "birne", "bison", "blass", "blatt", "blech", "blick", "blind",
+    "blitz", "blume", "bluse", "boden", "bogen", "bohne", "bombe", "bonus", "borte", "brand",
+    "braun", "braut", "breit", "brief", "brust", "buche", "chaos", "dachs", "dampf", "danke",
+    "datum", "decke", "degen", "deich", "depot", "diele", "dogge", "dosen", "draht", "drama",
+    "duett", "ebene", "ecken", "edler", "eiche", "eifer", "eimer", "eisen", "elend", "elfen",
+    "engel", "enkel", "enten", "erbse", "ernte", "essig", "etage", "fabel", "faden", "fahne",
+    "falke", "falle", "farbe", "feder", "feier", "feige", "felge", "ferne", "feuer", "fibel",
+    "filme", "firma", "fisch", "flach", "flora", "floss", "fluch", "fluss", "folge", "folie",
+    "forst", "frage", "frech", "frist", "frost", "fuchs", "funke", "gabel", "gasse", "geben",
+    "gehen", "geige", "geist", "gerte", "gicht", "glanz", "glatt", "gleis", "glied", "gnade",
+    "gotik", "griff", "grube", "gunst", "gurke", "hafen", "hagel", "haken", "halle", "handy",
+    "hasen", "hecke", "hefte", "heide", "heiss", "herde", "heute", "hexen", "hirte", "hobel",
+    "holen", "honig", "horde", "hosen", "hotel", "humor", "hunde", "ideal", "immer", "insel",
+    "jacke", "joker", "jubel", "kabel", "kakao", "kamel", "kamin", "kanal", "kanne", "kante",
+    "karte", "katze", "kegel", "kehle", "kelle", "kerbe", "kerze", "kette", "kiste", "klage",
+    "klang", "kleid", "klein", "klima", "kluge", "knabe", "knall", "knapp", "knopf", "kobra",
+    "kohle", "kraft", "krank", "kranz", "kreis", "krieg", "krone", "kugel", "kurve", "lager",
+    "lampe", "lanze", "laser", "laune", "leben", "leder", "lehre", "leine", "leise", "lesen",
+    "licht", "liebe", "linie", "liste", "loben", "logik", "lunge", "macht", "magen", "magie",
+    "malen", "maler", "mango", "marke", "markt", "maske", "mauer", "meile", "menge", "messe",
+    "meter", "miete", "minze", "monat", "motiv", "motor", "motte", "musik", "nacht", "nadel",
+    "nagel", "narbe", "nebel", "neffe", "nonne", "notiz", "nudel", "olive", "onkel", "opfer",
+    "optik", "orgel", "paket", "palme", "panne", "pasta", "pause", "pedal", "perle", "pfand",
+    "pferd", "pflug", "pilot", "pirat", "piste", "pizza", "platz", "plump", "polka", "preis",
+    "prinz", "probe", "pudel", "punkt", "puppe", "quark", "raben", "radar", "radio", "rasch",
+    "rasen", "ratte", "rauch", "regel", "regen", "reihe", "reise", "rinde", "rinne", "robbe",
+    "rolle", "roman", "rosen", "ruder", "rufen", "ruhig", "ruine", "runde", "sache", "sagen",
+    "saite", "salat", "salbe", "samen", "sauce", "schaf", "schal", "schar", "schon", "schub",
+    "schuh", "seele", "segel", "sehen", "seife", "seite", "senke", "sirup", "skala", "socke",
+    "sonne", "sorge", "spalt", "spatz", "speck", "spiel", "sporn", "sport", "staat", "stadt",
+    "stahl", "stall", "stamm", "stark", "steil", "stein", "stern", "stich", "stier", "still",
+    "stirn", "stoff", "stolz", "strom", "stuhl", "stumm", "sturm", "suche", "suppe", "tafel",
+    "tanne", "tante", "tasse", "taube", "tempo", "thema", "tiger", "tinte", "tisch", "tonne",
+    "torso", "torte", "trakt", "traum", "trend", "treue", "truhe", "trupp", "tulpe", "unmut",
+    "vater", "villa", "vogel", "waage", "wache", "waffe", "wagen", "walze", "wange", "wanne",
+    "warte", "weben", "weich", "weide", "welle", "wespe", "wiese", "wilde", "witwe", "woche",
+    "wolke", "wolle", "wonne", "wunde", "wurst", "zange", "zebra", "zeile", "zelle", "ziege",
+    "zunge", "zweck", "zwerg",
+];
+
+pub const SPANISH_FINAL: &[&str] = &[
+    "abril", "acero", "aguja", "aldea", "altar", "amigo", "ancho", "arena", "arroz", "atlas",
+    "avena", "baile", "balsa", "banco", "barco", "barro", "blusa", "bolsa", "borde", "brazo",
+    "brisa", "broma", "bueno", "burro", "cabra", "campo", "canal", "canto", "carne", "carta",
+    "causa", "cebra", "cerdo", "cielo", "cinta", "circo", "clave", "cobre", "coche", "color",
+    "corte", "crudo", "cuero", "culpa", "curva", "dedal", "diosa", "duelo", "dulce", "enero",
+    "erizo", "falda", "farol", "feliz", "fibra", "firma", "fleco", "flota", "fresa", "fruta",
+    "fuego", "gallo", "ganso", "gorra", "grano", "grasa", "grito", "gusto", "hacha", "hielo",
+    "hongo", "huevo", "humor", "ideal", "jaula", "juego", "labio", "largo", "leche", "lento",
+    "libro", "llama", "llave", "lucha", "madre", "mango", "manta", "marco", "marea", "menta",
+    "metal", "metro", "miedo", "monja", "monte", "moral", "mundo", "museo", "nariz", "nieve",
+    "noche", "novio", "olivo", "orden", "ostra", "padre", "palma", "papel", "pared", "pasto",
+    "patio", "pecho", "pelea", "perro", "piano", "pieza", "plato", "playa", "plaza", "pluma",
+    "poema", "polvo", "potro", "prado", "primo", "punto", "queso", "radio", "reina", "reloj",
+    "roble", "rueda", "ruido", "sabio", "salsa", "salto", "santo", "selva", "siglo", "silla",
+    "sobre", "suelo", "tabla", "tarde", "techo", "temor", "tigre", "torre", "traje", "trigo",
+    "tumba", "valle", "vapor", "verde", "viaje", "virus", "yegua", "zorro", "añejo", "araña",
+    "bañar", "ceñir", "dañar", "dueño", "niñez", "pañal", "puñal", "reñir", "señal", "señor",
+    "soñar", "sueño", "teñir",
+];
+
+pub const SPANISH_ACCEPTABLE: &[&str] = &[
+    "abril", "abrir", "acero", "aguja", "aldea", "altar", "amiga", "amigo", "ancho", "araña",
+    "arena", "arroz", "atlas", "avena", "añejo", "baile", "bajar", "balsa", "banco", "barco",
+    "barro", "bañar", "baños", "beber", "besos", "blusa", "bolsa", "borde", "botas", "brazo",
+    "brisa", "broma", "bueno", "burro", "cabra", "campo", "canal", "canto", "carne", "carta",
+    "causa", "cañas", "cebra", "cerdo", "ceñir", "cielo", "cinta", "circo", "clave", "cobre",
+    "coche", "color", "comer", "corte", "coser", "crudo", "cuero", "culpa", "curva", "dañar",
+    "dedal", "dejar", "diosa", "duelo", "dueña", "dueño", "dulce", "echar", "enero", "erizo",
+    "falda", "farol", "feliz", "fibra", "fijar", "firma", "fleco", "flota", "fresa", "fruta",
+    "fuego", "gafas", "gallo", "ganso", "girar", "gorra", "gotas", "grano", "grasa", "grito",
+    "gusto", "haber", "hacha", "hielo", "higos", "hongo", "huevo", "humor", "ideal", "jaula",
+    "juego", "jugar", "labio", "largo", "lavar", "leche", "lento", "leños", "libro", "llama",
+    "llave", "lucha", "madre", "mango", "manta", "marco", "marea", "menta", "mesas", "metal",
+    "metro", "miedo", "mirar", "monja", "monte", "moral", "mover", "mundo", "museo", "nadar",
+    "nariz", "nieve", "niñas", "niñez", "niños", "noche", "novio", "nubes", "obras", "olivo",
+    "ondas", "orden", "ostra", "padre", "pagar", "palma", "papel", "pared", "pasto", "patio",
+    "pañal", "pecho", "pelea", "perro", "pesar", "piano", "pieza", "piñas", "plato", "playa",
+    "plaza", "pluma", "poder", "poema", "polvo", "potro", "prado", "primo", "punto", "puñal",
+    "puños", "queso", "radio", "ramas", "reina", "reloj", "reñir", "risas", "roble", "rueda",
+    "ruido", "sabio", "sacar", "salir", "salsa", "salto", "santo", "selva", "señal", "señor",
+    "siglo", "silla", "sobre", "soñar", "subir", "suelo", "sueño", "tabla", "tarde", "techo",
+    "temor", "tener", "teñir", "tigre", "tocar", "tomar", "torre", "traje", "trigo", "tumba",
+    "vacas", "valle", "vapor", "velas", "venir", "verde", "viaje", "virus", "vivir", "volar",
+    "yegua", "zorro",
+];
@@ -0,0 +1,141 @@
+// Challenge codes: a word, its pack and difficulty packed into a code that can be sent
+// to a friend without revealing the answer at a glance.
+//
+// Layout before obfuscation: version, flags, letter count, pack name length, pack name,
+// word (UTF-8), then a FNV-1a checksum of everything before it. The bytes are XORed with
+// a fixed xorshift stream and written in Crockford base32.
+
+use clap::Subcommand;
+use crate::utils::WordDict;
+
+const VERSION: u8 = 1;
+const FLAG_DIFFICULT: u8 = 1;
+const BASE32: &[u8] = b"0123456789ABCDEFGHJKMNPQRSTVWXYZ";
+
+#[derive(Subcommand, Debug, Clone)]
+pub enum ChallengeAction {
+	/// Print a challenge code for WORD using the selected pack and difficulty
+	Create {
+		word: String,
+	},
+}
+
+pub struct Challenge {
+	pub word: String,
+	pub length: usize,
+	pub pack: String,
+	pub difficult: bool,
+}
+
+pub fn run(action: ChallengeAction, word_dict: &WordDict, pack: &str, difficult: bool) -> Result<(), Box<dyn std::error::Error>> {
+	match action {
+		ChallengeAction::Create { word } => {
+			let word = word_dict.alphabet.normalize(&word);
+			check_word(&word, word_dict)?;
+			let challenge = Challenge { word, length: word_dict.length, pack: String::from(pack), difficult };
+			println!("{}", encode(&challenge));
+			Ok(())
+		},
+	}
+}
+
+/// The error never contains the word, so that a bad code does not spoil the answer.
+pub fn check_word(word: &str, word_dict: &WordDict) -> Result<(), Box<dyn std::error::Error>> {
+	match word_dict.alphabet.indices(word) {
+		Some(letters) if letters.len() == word_dict.length && word_dict.vaild(word) => Ok(()),
+		_ => Err("the challenge word is not in the word list".into()),
+	}
+}
+
+pub fn encode(challenge: &Challenge) -> String {
+	let mut bytes = vec![VERSION, if challenge.difficult { FLAG_DIFFICULT } else { 0 },
+		challenge.length as u8, challenge.pack.len() as u8];
+	bytes.extend(challenge.pack.as_bytes());
+	bytes.extend(challenge.word.as_bytes());
+	bytes.extend(fnv1a(&bytes).to_be_bytes());
+	obfuscate(&mut bytes);
+	to_base32(&bytes)
+}
+
+pub fn decode(code: &str) -> Result<Challenge, Box<dyn std::error::Error>> {
+	let invalid = || -> Box<dyn std::error::Error> { format!("invalid challenge code {}", code).into() };
+	let mut bytes = from_base32(code).ok_or_else(invalid)?;
+	obfuscate(&mut bytes);
+	if bytes.len() < 8 {
+		return Err(invalid());
+	}
+	let (payload, checksum) = bytes.split_at(bytes.len() - 4);
+	if fnv1a(payload).to_be_bytes() != checksum {
+		return Err(format!("challenge code {} is damaged (checksum mismatch)", code).into());
+	}
+	if payload[0] != VERSION {
+		return Err(format!("challenge code {} has unsupported version {}", code, payload[0]).into());
+	}
+	let pack_len = payload[3] as usize;
+	if payload.len() < 4 + pack_len {
+		return Err(invalid());
+	}
+	let pack = String::from_utf8(payload[4..4 + pack_len].to_vec()).map_err(|_| invalid())?;
+	let word = String::from_utf8(payload[4 + pack_len..].to_vec()).map_err(|_| invalid())?;
+	Ok(Challenge { word, length: payload[2] as usize, pack, difficult: payload[1] & FLAG_DIFFICULT != 0 })
+}
+
+fn fnv1a(bytes: &[u8]) -> u32 {
+	let mut hash: u32 = 0x811c9dc5;
+	for byte in bytes {
+		hash ^= *byte as u32;
+		hash = hash.wrapping_mul(0x01000193);
+	}
+	hash
+}
+
+/// XOR with a fixed keystream, applying it twice gives the input back.
+fn obfuscate(bytes: &mut [u8]) {
+	let mut state: u32 = 0x9e3779b9;
+	for byte in bytes.iter_mut() {
+		state ^= state << 13;
+		state ^= state >> 17;
+		state ^= state << 5;
+		*byte ^= (state >> 24) as u8;
+	}
+}
+
+fn to_base32(bytes: &[u8]) -> String {
+	let mut code = String::new();
+	let mut buffer: u32 = 0;
+	let mut bits = 0;
+	for byte in bytes {
+		buffer = (buffer << 8) | *byte as u32;
+		bits += 8;
+		while bits >= 5 {
+			bits -= 5;
+			code.push(BASE32[((buffer >> bits) & 31) as usize] as char);
+		}
+	}
+	if bits > 0 {
+		code.push(BASE32[((buffer << (5 - bits)) & 31) as usize] as char);
+	}
+	code
+}
+
+/// Case-insensitive, dashes and spaces are ignored, O/I/L read as 0/1/1.
+fn from_base32(code: &str) -> Option<Vec<u8>> {
+	let mut bytes = Vec::new();
+	let mut buffer: u32 = 0;
+	let mut bits = 0;
+	for ch in code.chars().filter(|ch| *ch != '-' && !ch.is_whitespace()) {
+		let ch = match ch.to_ascii_uppercase() {
+			'O' => '0',
+			'I' | 'L' => '1',
+			ch => ch,
+		};
+		let value = BASE32.iter().position(|val| *val as char == ch)? as u32;
+		buffer = (buffer << 5) | value;
+		bits += 5;
+		if bits >= 8 {
+			bits -= 8;
+			bytes.push((buffer >> bits) as u8);
+		}
+	}
+	Some(bytes)
+}
@@ -0,0 +1,33 @@
+// Calendar-based daily puzzles: everyone playing on the same date gets the same puzzle number
+
+use chrono::{DateTime, FixedOffset, Local, NaiveDate};
+
+/// The day of puzzle #0 unless `--epoch` says otherwise
+pub const DEFAULT_EPOCH: &str = "2021-06-19";
+
+pub fn parse_date(raw: &str) -> Result<NaiveDate, Box<dyn std::error::Error>> {
+	NaiveDate::parse_from_str(raw.trim(), "%Y-%m-%d")
+		.map_err(|err| format!("invalid date {:?} (expected YYYY-MM-DD): {}", raw, err).into())
+}
+
+/// The environment variable that sets the clock, an RFC 3339 time, so runs can be repeated
+pub const NOW_VARIABLE: &str = "WORDLE_NOW";
+
+pub fn now() -> DateTime<FixedOffset> {
+	std::env::var(NOW_VARIABLE).ok()
+		.and_then(|now| DateTime::parse_from_rfc3339(now.trim()).ok())
+		.unwrap_or_else(|| Local::now().fixed_offset())
+}
+
+pub fn today() -> NaiveDate {
+	now().date_naive()
+}
+
+/// Number of days from `epoch` to `date`.
+pub fn puzzle_number(date: NaiveDate, epoch: NaiveDate) -> Result<usize, Box<dyn std::error::Error>> {
+	let days = date.signed_duration_since(epoch).num_days();
+	if days < 0 {
+		return Err(format!("{} is before the daily epoch {}", date, epoch).into());
+	}
+	Ok(days as usize)
+}
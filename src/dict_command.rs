@@ -0,0 +1,157 @@
+// Tooling for curating word lists: `wordle dict ...`
+
+use clap::{Subcommand, ValueEnum};
+use std::{collections::{BTreeMap, BTreeSet}, fs, path::Path};
+use crate::utils::{self, WordDict};
+use crate::word_pack::{self, WordPack};
+
+#[derive(Subcommand, Debug, Clone)]
+pub enum DictAction {
+	/// Run every validation on the configured final and acceptable lists
+	Check,
+	/// Show the words that only appear in one of two lists
+	Diff {
+		a: String,
+		b: String,
+	},
+	/// Union several lists into one sorted list without duplicates
+	Merge {
+		#[arg(required = true)]
+		files: Vec<String>,
+		#[arg(short, long)]
+		output: Option<String>,
+	},
+	/// Letter frequencies, positional frequencies and a length histogram
+	Stats {
+		/// A word list file, the configured final list is used if omitted
+		file: Option<String>,
+	},
+	/// Write a list of the selected word pack, one word per line
+	Export {
+		#[arg(value_enum)]
+		list: ListKind,
+		#[arg(short, long)]
+		output: Option<String>,
+	},
+	/// List the bundled and user-installed word packs
+	Packs,
+}
+
+#[derive(ValueEnum, Debug, Clone, Copy)]
+pub enum ListKind {
+	Final,
+	Acceptable,
+}
+
+pub fn run(action: DictAction, pack: &WordPack, final_set: Option<String>, acceptable_set: Option<String>,
+	pack_dir: Option<&Path>) -> Result<(), Box<dyn std::error::Error>> {
+	match action {
+		DictAction::Check => {
+			let mut word_dict = WordDict::new();
+			word_dict.load(pack, final_set, acceptable_set)?;
+			let problems = word_dict.check();
+			println!("final: {} words, acceptable: {} words", word_dict.final_list.len(), word_dict.acceptable_list().len());
+			for problem in &problems {
+				println!("{}", problem);
+			}
+			if problems.is_empty() {
+				println!("OK");
+				Ok(())
+			}
+			else {
+				Err(format!("{} problems found", problems.len()).into())
+			}
+		},
+		DictAction::Diff { a, b } => {
+			let a_set: BTreeSet<String> = utils::load_word_file(&a)?.iter().map(|word| utils::normalize_word(word)).collect();
+			let b_set: BTreeSet<String> = utils::load_word_file(&b)?.iter().map(|word| utils::normalize_word(word)).collect();
+			for word in a_set.difference(&b_set) {
+				println!("- {}", word);
+			}
+			for word in b_set.difference(&a_set) {
+				println!("+ {}", word);
+			}
+			println!("{} common, {} only in {}, {} only in {}", a_set.intersection(&b_set).count(),
+				a_set.difference(&b_set).count(), a, b_set.difference(&a_set).count(), b);
+			Ok(())
+		},
+		DictAction::Merge { files, output } => {
+			let mut merged = BTreeSet::new();
+			for file in &files {
+				merged.extend(utils::load_word_file(file)?.iter().map(|word| utils::normalize_word(word)));
+			}
+			write_list(merged.iter().map(|word| word.to_lowercase()), output)
+		},
+		DictAction::Stats { file } => {
+			let words = match file {
+				Some(file) => utils::load_word_file(&file)?.iter().map(|word| utils::normalize_word(word)).collect(),
+				None => {
+					let mut word_dict = WordDict::new();
+					word_dict.load(pack, final_set, None)?;
+					word_dict.final_list
+				},
+			};
+			print_stats(&words);
+			Ok(())
+		},
+		DictAction::Export { list, output } => {
+			let words = match list {
+				ListKind::Final => &pack.final_list,
+				ListKind::Acceptable => &pack.acceptable_list,
+			};
+			write_list(words.iter().map(|word| word.to_lowercase()), output)
+		},
+		DictAction::Packs => {
+			for pack in word_pack::list(pack_dir) {
+				println!("{} ({}): {} letters, {} final, {} acceptable words", pack.name, pack.language,
+					pack.length, pack.final_list.len(), pack.acceptable_list.len());
+			}
+			Ok(())
+		},
+	}
+}
+
+fn write_list(words: impl Iterator<Item = String>, output: Option<String>) -> Result<(), Box<dyn std::error::Error>> {
+	let mut content = String::new();
+	for word in words {
+		content.push_str(&word);
+		content.push('\n');
+	}
+	match output {
+		Some(address) => fs::write(address, content)?,
+		None => print!("{}", content),
+	}
+	Ok(())
+}
+
+fn print_stats(words: &[String]) {
+	let mut letters: BTreeMap<String, usize> = BTreeMap::new();
+	let mut positions: BTreeMap<String, Vec<usize>> = BTreeMap::new();
+	let mut lengths: BTreeMap<usize, usize> = BTreeMap::new();
+	let words: Vec<Vec<String>> = words.iter().map(|word| utils::split_letters(word)).collect();
+	let longest = words.iter().map(|word| word.len()).max().unwrap_or(0);
+	for word in &words {
+		*lengths.entry(word.len()).or_insert(0) += 1;
+		for (i, ch) in word.iter().enumerate() {
+			*letters.entry(ch.clone()).or_insert(0) += 1;
+			positions.entry(ch.clone()).or_insert_with(|| vec![0; longest])[i] += 1;
+		}
+	}
+
+	println!("Words: {}", words.len());
+	println!("Length histogram:");
+	for (length, count) in &lengths {
+		println!("{:>3} {:>6}", length, count);
+	}
+	println!("Letter frequencies:");
+	let mut by_count: Vec<(&String, &usize)> = letters.iter().collect();
+	by_count.sort_by(|a, b| b.1.cmp(a.1).then(a.0.cmp(b.0)));
+	for (ch, count) in by_count {
+		println!("{} {:>6}", ch, count);
+	}
+	println!("Positional frequencies:");
+	for (ch, counts) in &positions {
+		let row: Vec<String> = counts.iter().map(|count| format!("{:>5}", count)).collect();
+		println!("{} {}", ch, row.join(" "));
+	}
+}
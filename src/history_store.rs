@@ -0,0 +1,213 @@
+// Where the game history of --state is kept
+//
+// The scheme in front of the address picks the store:
+//   json:ADDRESS     the whole history as one JSON document, written again after every game
+//   jsonl:ADDRESS    an append-only log, one JSON record per line
+//   sqlite:ADDRESS   an SQLite database
+// An address without a known scheme is a JSON file, as before.
+
+use std::collections::BTreeMap;
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use rusqlite::{params, Connection, Transaction};
+use serde::{Deserialize, Serialize};
+use crate::json_parser::{self, Gamejson, Games};
+use crate::signing::{self, SignedStore};
+
+pub trait HistoryStore {
+	/// The saved history, empty if nothing was saved yet
+	fn load(&mut self) -> Result<Gamejson, Box<dyn std::error::Error>>;
+
+	/// Save `gamejson`, which holds the games loaded or saved before followed by the new ones
+	fn save(&mut self, gamejson: &Gamejson) -> Result<(), Box<dyn std::error::Error>>;
+
+	/// Replace the whole history, for changes to games already saved
+	fn rewrite(&mut self, gamejson: &Gamejson) -> Result<(), Box<dyn std::error::Error>>;
+}
+
+pub fn open(state: &str) -> Result<Box<dyn HistoryStore>, Box<dyn std::error::Error>> {
+	let store: Box<dyn HistoryStore> = match state.split_once(':') {
+		Some(("json", address)) => Box::new(JsonFile { address: String::from(address) }),
+		Some(("jsonl", address)) => Box::new(JsonLines::new(address)),
+		Some(("sqlite", address)) => Box::new(Sqlite::open(address)?),
+		_ => Box::new(JsonFile { address: String::from(state) }),
+	};
+	Ok(store)
+}
+
+/// The store of --state, if any, and the history it holds
+pub type Opened = (Option<Box<dyn HistoryStore>>, Gamejson);
+
+/// Open the store of --state and load its history, an empty one without --state. With
+/// a signing key the games added later are signed.
+pub fn load(state: Option<&str>, sign_key: Option<&str>) -> Result<Opened, Box<dyn std::error::Error>> {
+	match state {
+		Some(state) => {
+			let mut store = open(state)?;
+			if let Some(address) = sign_key {
+				store = Box::new(SignedStore::new(store, signing::read_key(address)?));
+			}
+			let gamejson = store.load()?;
+			Ok((Some(store), gamejson))
+		},
+		None => Ok((None, Gamejson::new())),
+	}
+}
+
+struct JsonFile {
+	address: String,
+}
+
+impl HistoryStore for JsonFile {
+	fn load(&mut self) -> Result<Gamejson, Box<dyn std::error::Error>> {
+		match fs::read_to_string(&self.address) {
+			Ok(raw_json) => json_parser::read_json(&raw_json)
+				.map_err(|err| format!("the state file {} is not valid: {}", self.address, err).into()),
+			Err(_) => Ok(Gamejson::new()),
+		}
+	}
+
+	fn save(&mut self, gamejson: &Gamejson) -> Result<(), Box<dyn std::error::Error>> {
+		fs::write(&self.address, serde_json::to_string(gamejson)?)?;
+		Ok(())
+	}
+
+	fn rewrite(&mut self, gamejson: &Gamejson) -> Result<(), Box<dyn std::error::Error>> {
+		self.save(gamejson)
+	}
+}
+
+/// One line of the log. The match score is written whole whenever it changes.
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+enum Record {
+	Game(Games),
+	MatchScore(BTreeMap<String, i32>),
+	Achievement(String),
+}
+
+struct JsonLines {
+	address: String,
+	/// What the log already holds
+	games: usize,
+	match_score: BTreeMap<String, i32>,
+	achievements: usize,
+}
+
+impl JsonLines {
+	fn new(address: &str) -> JsonLines {
+		JsonLines { address: String::from(address), games: 0, match_score: BTreeMap::new(), achievements: 0 }
+	}
+}
+
+impl HistoryStore for JsonLines {
+	fn load(&mut self) -> Result<Gamejson, Box<dyn std::error::Error>> {
+		let mut gamejson = Gamejson::new();
+		let content = fs::read_to_string(&self.address).unwrap_or_default();
+		for (number, line) in content.lines().enumerate() {
+			if line.trim().is_empty() {
+				continue;
+			}
+			match serde_json::from_str(line) {
+				Ok(Record::Game(game)) => gamejson.add_games(game),
+				Ok(Record::MatchScore(match_score)) => gamejson.set_match_score(match_score),
+				Ok(Record::Achievement(id)) => gamejson.add_achievement(&id),
+				Err(err) => return Err(format!("line {} of {} is not valid: {}", number + 1, self.address, err).into()),
+			}
+		}
+		self.games = gamejson.games().len();
+		self.match_score = gamejson.match_score().clone();
+		self.achievements = gamejson.achievements().len();
+		Ok(gamejson)
+	}
+
+	fn save(&mut self, gamejson: &Gamejson) -> Result<(), Box<dyn std::error::Error>> {
+		let mut records: Vec<Record> = gamejson.games()[self.games..].iter().cloned().map(Record::Game).collect();
+		if *gamejson.match_score() != self.match_score {
+			records.push(Record::MatchScore(gamejson.match_score().clone()));
+		}
+		records.extend(gamejson.achievements()[self.achievements..].iter().cloned().map(Record::Achievement));
+		let mut content = String::new();
+		for record in &records {
+			content.push_str(&serde_json::to_string(record)?);
+			content.push('\n');
+		}
+		OpenOptions::new().create(true).append(true).open(&self.address)?.write_all(content.as_bytes())?;
+		self.games = gamejson.games().len();
+		self.match_score = gamejson.match_score().clone();
+		self.achievements = gamejson.achievements().len();
+		Ok(())
+	}
+
+	fn rewrite(&mut self, gamejson: &Gamejson) -> Result<(), Box<dyn std::error::Error>> {
+		fs::write(&self.address, "")?;
+		*self = JsonLines::new(&self.address);
+		self.save(gamejson)
+	}
+}
+
+/// Every game is a row holding its JSON record, so new fields of `Games` need no migration
+struct Sqlite {
+	connection: Connection,
+}
+
+impl Sqlite {
+	fn open(address: &str) -> Result<Sqlite, Box<dyn std::error::Error>> {
+		let connection = Connection::open(address)?;
+		connection.execute_batch(
+			"CREATE TABLE IF NOT EXISTS games (id INTEGER PRIMARY KEY, record TEXT NOT NULL);
+			CREATE TABLE IF NOT EXISTS match_score (player TEXT PRIMARY KEY, points INTEGER NOT NULL);
+			CREATE TABLE IF NOT EXISTS achievements (position INTEGER PRIMARY KEY, id TEXT NOT NULL UNIQUE);")?;
+		Ok(Sqlite { connection })
+	}
+}
+
+impl HistoryStore for Sqlite {
+	fn load(&mut self) -> Result<Gamejson, Box<dyn std::error::Error>> {
+		let mut gamejson = Gamejson::new();
+		let mut games = self.connection.prepare("SELECT record FROM games ORDER BY id")?;
+		for record in games.query_map([], |row| row.get::<_, String>(0))? {
+			gamejson.add_games(serde_json::from_str(&record?)?);
+		}
+		let mut match_score = self.connection.prepare("SELECT player, points FROM match_score")?;
+		let match_score = match_score.query_map([], |row| Ok((row.get(0)?, row.get(1)?)))?
+			.collect::<Result<BTreeMap<String, i32>, _>>()?;
+		gamejson.set_match_score(match_score);
+		let mut achievements = self.connection.prepare("SELECT id FROM achievements ORDER BY position")?;
+		for id in achievements.query_map([], |row| row.get::<_, String>(0))? {
+			gamejson.add_achievement(&id?);
+		}
+		Ok(gamejson)
+	}
+
+	fn save(&mut self, gamejson: &Gamejson) -> Result<(), Box<dyn std::error::Error>> {
+		let transaction = self.connection.transaction()?;
+		insert_new(&transaction, gamejson)?;
+		transaction.commit()?;
+		Ok(())
+	}
+
+	fn rewrite(&mut self, gamejson: &Gamejson) -> Result<(), Box<dyn std::error::Error>> {
+		let transaction = self.connection.transaction()?;
+		transaction.execute_batch("DELETE FROM games; DELETE FROM achievements;")?;
+		insert_new(&transaction, gamejson)?;
+		transaction.commit()?;
+		Ok(())
+	}
+}
+
+/// Add what the database does not hold yet
+fn insert_new(transaction: &Transaction, gamejson: &Gamejson) -> Result<(), Box<dyn std::error::Error>> {
+	let saved: usize = transaction.query_row("SELECT COUNT(*) FROM games", [], |row| row.get(0))?;
+	for (id, game) in gamejson.games().iter().enumerate().skip(saved) {
+		transaction.execute("INSERT INTO games (id, record) VALUES (?1, ?2)", params![id + 1, serde_json::to_string(game)?])?;
+	}
+	transaction.execute("DELETE FROM match_score", [])?;
+	for (player, points) in gamejson.match_score() {
+		transaction.execute("INSERT INTO match_score (player, points) VALUES (?1, ?2)", params![player, points])?;
+	}
+	for (position, id) in gamejson.achievements().iter().enumerate() {
+		transaction.execute("INSERT OR IGNORE INTO achievements (position, id) VALUES (?1, ?2)", params![position + 1, id])?;
+	}
+	Ok(())
+}
@@ -0,0 +1,61 @@
+// Two-player hotseat mode: players take turns choosing the answer for each other
+
+use std::io::{self, Write};
+use termion::input::TermRead;
+use crate::json_parser::Gamejson;
+use crate::utils::{self, WordDict};
+
+/// Ask `setter` for the answer without echoing it. Words outside the final list are refused.
+pub fn read_secret_word(setter: &str, guesser: &str, word_dict: &WordDict, is_tty: bool) -> Result<String, Box<dyn std::error::Error>> {
+	loop {
+		if is_tty {
+			utils::clear_command_screen();
+			print!("{}, choose a word for {} (hidden): ", setter, guesser);
+			io::stdout().flush()?;
+		}
+		let line = if is_tty {
+			let line = io::stdin().lock().read_passwd(&mut io::stdout())?;
+			println!();
+			line
+		}
+		else{
+			let mut line = String::new();
+			match io::stdin().read_line(&mut line)? {
+				0 => None,
+				_ => Some(line),
+			}
+		};
+		let word = match line {
+			Some(line) => word_dict.alphabet.normalize(&line),
+			None => return Err("input ended before the answer was chosen".into()),
+		};
+		if word_dict.is_final(&word) {
+			if is_tty {
+				utils::clear_command_screen();
+				println!("{}, it is your turn to guess!", guesser);
+			}
+			return Ok(word);
+		}
+		if is_tty {
+			println!("That word is not in the answer list, try another one.");
+		}
+		else{
+			println!("INVALID");
+		}
+	}
+}
+
+/// The guesser scores a point for solving the puzzle, otherwise the setter does.
+pub fn score_round(gamejson: &mut Gamejson, guesser: &str, setter: &str, solved: bool) {
+	gamejson.add_points(if solved { guesser } else { setter }, 1);
+}
+
+pub fn print_score(gamejson: &Gamejson, players: &[String], is_tty: bool) {
+	let scores: Vec<String> = players.iter().map(|player| format!("{} {}", player, gamejson.get_points(player))).collect();
+	if is_tty {
+		println!("Score: {}", scores.join(" - "));
+	}
+	else{
+		println!("SCORE {}", scores.join(" "));
+	}
+}
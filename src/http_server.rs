@@ -0,0 +1,204 @@
+// `wordle http`: games hosted behind a small JSON API
+//
+//   POST /games              {"seed": S, "day": N, "difficult": B}, every field optional;
+//                            without seed and day the answer is random -> the new board
+//   GET  /games/<id>         the board
+//   POST /games/<id>/guesses {"guess": "CRANE"} -> the board, or 400 if the guess is invalid
+//   GET  /stats              statistics of every finished game in the state file; with
+//                            --sign-key only of the signed games that verify, and a
+//                            "verification" of the history: {"verified", "signed", "unsigned", "broken"}
+//
+// A board is {"id", "status": running|won|lost, "difficult", "length", "chances",
+// "alphabet", "guesses": [{"word", "result": "GYRRR"}], "keyboard"}, where "keyboard" has
+// one color per letter of "alphabet", plus "answer" once the game is over.
+//
+// The answer is never sent while the game is running. Finished games are recorded in
+// the --state file like in the interactive mode.
+
+use std::collections::HashMap;
+use std::time::{SystemTime, UNIX_EPOCH};
+use clap::Args;
+use serde::Deserialize;
+use serde_json::{json, Value};
+use tiny_http::{Header, Method, Request, Response, Server};
+use crate::history_store::HistoryStore;
+use crate::interact_model::{GameInfo, GUESS_CHANCE};
+use crate::json_parser::Gamejson;
+use crate::signing::Counted;
+use crate::shuffle::{self, ShuffleVersion};
+use crate::utils::WordDict;
+
+#[derive(Args, Debug, Clone)]
+pub struct HttpArgs {
+	/// Port to listen on, 0 picks a free one
+	#[arg(long, default_value_t = 8080)]
+	port: u16,
+
+	/// Address to bind to
+	#[arg(long, default_value = "127.0.0.1")]
+	bind: String,
+}
+
+#[derive(Deserialize, Default)]
+struct NewGame {
+	seed: Option<u64>,
+	day: Option<usize>,
+	difficult: Option<bool>,
+}
+
+#[derive(Deserialize)]
+struct Guess {
+	guess: String,
+}
+
+/// Settings shared by every hosted game
+pub struct Host<'a> {
+	pub word_dict: &'a WordDict,
+	pub shuffle_version: ShuffleVersion,
+	pub difficult: bool,
+	pub pack: String,
+	pub store: Option<Box<dyn HistoryStore>>,
+	/// What the statistics count, see `signing::Counted`
+	pub counted: Counted,
+	pub gamejson: Gamejson,
+}
+
+struct Hosted<'a> {
+	host: Host<'a>,
+	games: HashMap<usize, GameInfo>,
+	next_id: usize,
+}
+
+pub fn run(http_args: HttpArgs, host: Host, is_tty: bool) -> Result<(), Box<dyn std::error::Error>> {
+	let server = Server::http((http_args.bind.as_str(), http_args.port)).map_err(|err| err.to_string())?;
+	let address = server.server_addr().to_ip().ok_or("the server is not listening on an IP address")?;
+	if is_tty {
+		println!("Wordle API listening on http://{}", address);
+	}
+	else{
+		println!("LISTENING {}", address);
+	}
+
+	let mut hosted = Hosted { host, games: HashMap::new(), next_id: 1 };
+	for mut request in server.incoming_requests() {
+		let mut body = String::new();
+		let (status, reply) = match request.as_reader().read_to_string(&mut body) {
+			Ok(_) => hosted.handle(request.method(), request.url(), &body),
+			Err(_) => error(400, "the request body is not UTF-8"),
+		};
+		respond(request, status, reply)?;
+	}
+	Ok(())
+}
+
+fn respond(request: Request, status: u16, reply: Value) -> Result<(), Box<dyn std::error::Error>> {
+	let header = Header::from_bytes("Content-Type", "application/json").unwrap();
+	request.respond(Response::from_string(reply.to_string()).with_status_code(status).with_header(header))?;
+	Ok(())
+}
+
+fn error(status: u16, message: &str) -> (u16, Value) {
+	(status, json!({ "error": message }))
+}
+
+impl Hosted<'_> {
+	fn handle(&mut self, method: &Method, url: &str, body: &str) -> (u16, Value) {
+		let path: Vec<&str> = url.split('?').next().unwrap_or("").split('/').filter(|part| !part.is_empty()).collect();
+		match (method, path.as_slice()) {
+			(Method::Post, ["games"]) => self.create(body),
+			(Method::Get, ["games", id]) => match self.find(id) {
+				Some((id, gameinfo)) => (200, board(id, gameinfo, self.host.word_dict)),
+				None => error(404, "no such game"),
+			},
+			(Method::Post, ["games", id, "guesses"]) => self.guess(id, body),
+			(Method::Get, ["stats"]) => (200, self.host.counted.to_json()),
+			(_, ["games"]) | (_, ["games", _]) | (_, ["games", _, "guesses"]) | (_, ["stats"]) => error(405, "method not allowed"),
+			_ => error(404, "not found"),
+		}
+	}
+
+	fn find(&self, id: &str) -> Option<(usize, &GameInfo)> {
+		let id = id.parse().ok()?;
+		self.games.get(&id).map(|gameinfo| (id, gameinfo))
+	}
+
+	fn create(&mut self, body: &str) -> (u16, Value) {
+		let new_game: NewGame = if body.trim().is_empty() {
+			NewGame::default()
+		}
+		else{
+			match serde_json::from_str(body) {
+				Ok(new_game) => new_game,
+				Err(err) => return error(400, &err.to_string()),
+			}
+		};
+		let (seed, day) = match (new_game.seed, new_game.day) {
+			(None, None) => (random_seed(), 1),
+			(seed, day) => (seed.unwrap_or(shuffle::DEFAULT_SEED), day.unwrap_or(1)),
+		};
+		let answer = match self.host.word_dict.day_answer(seed, day, self.host.shuffle_version) {
+			Ok(answer) => answer,
+			Err(err) => return error(400, &err.to_string()),
+		};
+		let id = self.next_id;
+		self.next_id += 1;
+		let gameinfo = GameInfo::new(&answer, new_game.difficult.unwrap_or(self.host.difficult), self.host.word_dict);
+		let reply = board(id, &gameinfo, self.host.word_dict);
+		self.games.insert(id, gameinfo);
+		(201, reply)
+	}
+
+	fn guess(&mut self, id: &str, body: &str) -> (u16, Value) {
+		let guess: Guess = match serde_json::from_str(body) {
+			Ok(guess) => guess,
+			Err(err) => return error(400, &err.to_string()),
+		};
+		let word_dict = self.host.word_dict;
+		let (id, gameinfo) = match id.parse().ok().and_then(|id| self.games.get_mut(&id).map(|gameinfo| (id, gameinfo))) {
+			Some(found) => found,
+			None => return error(404, "no such game"),
+		};
+		if !gameinfo.game_is_running() {
+			return error(409, "the game is over");
+		}
+		if gameinfo.make_guess(&word_dict.alphabet.normalize(&guess.guess), word_dict).is_err() {
+			return error(400, "invalid guess");
+		}
+		let reply = board(id, gameinfo, word_dict);
+		if !gameinfo.game_is_running() {
+			let game = gameinfo.to_game(&self.host.pack);
+			self.host.counted.add_game(game.clone());
+			self.host.gamejson.add_games(game);
+			if let Some(store) = self.host.store.as_mut() {
+				if let Err(err) = store.save(&self.host.gamejson) {
+					return error(500, &format!("the game could not be saved: {}", err));
+				}
+			}
+		}
+		(200, reply)
+	}
+}
+
+fn board(id: usize, gameinfo: &GameInfo, word_dict: &WordDict) -> Value {
+	let guesses: Vec<Value> = gameinfo.guess_history().iter()
+		.map(|(word, result)| json!({ "word": word, "result": result }))
+		.collect();
+	let mut board = json!({
+		"id": id,
+		"status": gameinfo.status(),
+		"difficult": gameinfo.is_difficult(),
+		"length": word_dict.length,
+		"chances": GUESS_CHANCE,
+		"alphabet": word_dict.alphabet.letters(),
+		"guesses": guesses,
+		"keyboard": gameinfo.keyboard(),
+	});
+	if !gameinfo.game_is_running() {
+		board["answer"] = json!(gameinfo.answer());
+	}
+	board
+}
+
+fn random_seed() -> u64 {
+	SystemTime::now().duration_since(UNIX_EPOCH).map(|time| time.as_nanos() as u64).unwrap_or(0)
+}
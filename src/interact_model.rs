@@ -4,7 +4,7 @@ use crate::utils::{self, Stats};
 use std::io;
 use crate::json_parser::Games;
 
-const GUESS_CHANCE: i32 = 6;
+pub const GUESS_CHANCE: i32 = 6;
 
 pub enum GameStatus{
 	Running,
@@ -13,16 +13,72 @@ pub enum GameStatus{
 }
 
 pub struct GameInfo{
-	keyboard_status: [char; 26],
-	guess_answer: Vec<char>,
+	keyboard_status: Vec<char>,
+	letters: Vec<String>,
+	guess_answer: Vec<usize>,
 	guess_history: Vec<(String,String)>,
 	game_status: GameStatus,
 	is_difficult: bool,
 }
 
+/// Score `guess` against `answer`, both given as alphabet indices.
+/// Returns one of 'G', 'Y', 'R' for every letter of the guess.
+pub fn score_guess(answer: &[usize], guess: &[usize], alphabet_size: usize) -> String {
+	let mut guess_status = String::new();
+	let mut cnt = vec![0; alphabet_size];
+	for id in answer{
+		cnt[*id] += 1;
+	}
+	/*  fix: you should firstly consider green character.
+		example:
+		answer = START
+		user_input = ABAND
+	*/
+	for (id, ans) in guess.iter().zip(answer){
+		if id == ans{
+			cnt[*id] -= 1;
+		}
+	}
+	for (id, ans) in guess.iter().zip(answer){
+		let res = if id == ans{
+			'G'
+		}
+		else if cnt[*id] > 0{
+			cnt[*id] -= 1;
+			'Y'
+		}
+		else{
+			'R'
+		};
+		guess_status.push(res);
+	}
+	guess_status
+}
+
 impl GameInfo{
-	pub fn new(answer: &str, is_difficult: bool) -> GameInfo {
-		GameInfo { keyboard_status: ['X'; 26], guess_answer: String::from(answer).chars().collect(), guess_history: Vec::new(), game_status: GameStatus::Running, is_difficult: is_difficult}
+	/// `answer` must only use letters of the dictionary's alphabet.
+	pub fn new(answer: &str, is_difficult: bool, word_dict: &utils::WordDict) -> GameInfo {
+		let alphabet = &word_dict.alphabet;
+		GameInfo { keyboard_status: vec!['X'; alphabet.len()], letters: alphabet.letters().to_vec(),
+			guess_answer: alphabet.indices(answer).expect("answer uses letters outside the alphabet"),
+			guess_history: Vec::new(), game_status: GameStatus::Running, is_difficult: is_difficult}
+	}
+
+	pub fn guess_history(&self) -> &[(String, String)] {
+		&self.guess_history
+	}
+
+	pub fn is_difficult(&self) -> bool {
+		self.is_difficult
+	}
+
+	/// Some(true) if the answer was found, Some(false) if all chances are used up
+	pub fn result(&self) -> Option<bool> {
+		match self.game_status {
+			GameStatus::Running => None, 
+			GameStatus::Success => Some(true), 
+			GameStatus::Fail => Some(false),
+		}
 	}
 
 	pub fn game_is_running(&self) -> bool {
@@ -38,7 +94,7 @@ impl GameInfo{
 		}
 	}
 
-	fn difficult_vaild(&mut self, user_input: &str) -> bool {
+	fn difficult_vaild(&mut self, user_input: &[usize], word_dict: &utils::WordDict) -> bool {
 		match self.is_difficult {
 			false => true,
 			true => {
@@ -47,23 +103,23 @@ impl GameInfo{
 				}
 				else{
 					let (last_input, last_color) = self.guess_history.last().unwrap();
-					let tmp = last_input.chars().zip(last_color.chars());
-					let _tmp = String::from(user_input);
-					let tmp = tmp.zip(_tmp.chars());
-					let mut last_cnt = [0; 26];
-					let mut now_cnt = [0; 26];
+					let last_input = word_dict.alphabet.indices(last_input).unwrap();
+					let tmp = last_input.iter().zip(last_color.chars());
+					let tmp = tmp.zip(user_input.iter());
+					let mut last_cnt = vec![0; self.letters.len()];
+					let mut now_cnt = vec![0; self.letters.len()];
 					for ((last_char, last_col), now_char) in tmp {
 						if last_col == 'G' && last_char != now_char {
 							return false;
 						}
 						if last_col == 'Y' {
-							last_cnt[(last_char as usize) - ('A' as usize)] += 1;
+							last_cnt[*last_char] += 1;
 						}
 						if last_col != 'G' {
-							now_cnt[(now_char as usize) - ('A' as usize)] += 1;
+							now_cnt[*now_char] += 1;
 						}
 					}
-					for i in 0_usize..26_usize {
+					for i in 0_usize..self.letters.len() {
 						if now_cnt[i] < last_cnt[i]{
 							return false;
 						}
@@ -78,46 +134,23 @@ impl GameInfo{
 		/* We assume that guess_answer are vaild
 		give in a user_guess, update the status for keyboard, screen, ...
 		*/
-		if !(word_dict.vaild(user_input) && self.difficult_vaild(user_input)){
+		if !word_dict.vaild(user_input){
 			return Err(())
 		}
-		let user_input = String::from(user_input);
-		let t:Vec<char> = user_input.chars().collect();
-		let mut guess_status = String::new();
-		let mut flag = true;
-		let mut cnt = [0; 26];
-		for ch in &self.guess_answer{
-			cnt[(*ch as usize) - ('A' as usize)] += 1;
-		}
-		/*  fix: you should firstly consider green character.
-			example:
-			answer = START
-			user_input = ABAND
-		*/
-		for i in 0..t.len(){
-			let id = (t[i] as usize) - ('A' as usize);
-			if t[i] == self.guess_answer[i]{
-				cnt[id] -= 1;
-			}
+		let t = match word_dict.alphabet.indices(user_input) {
+			Some(t) if t.len() == self.guess_answer.len() => t,
+			_ => return Err(()),
+		};
+		if !self.difficult_vaild(&t, word_dict){
+			return Err(())
 		}
-		for i in 0..t.len(){
-			// println!("{}, {}",t[i] as usize, 'A' as usize);
-			let id = (t[i] as usize) - ('A' as usize);
-			let res = if t[i] == self.guess_answer[i]{
-				'G'
-			}
-			else if cnt[id] > 0{
-				cnt[id] -= 1;
-				'Y'
-			}
-			else{
-				'R'
-			};
+		let guess_status = score_guess(&self.guess_answer, &t, self.letters.len());
+		let mut flag = true;
+		for (id, res) in t.iter().zip(guess_status.chars()){
 			flag &= res == 'G';
-			self.change_keyboard_status(id, res);
-			guess_status.push(res);
+			self.change_keyboard_status(*id, res);
 		}
-		self.guess_history.push((user_input, guess_status));
+		self.guess_history.push((String::from(user_input), guess_status));
 		if flag{
 			self.game_status = GameStatus::Success;
 		}
@@ -127,6 +160,50 @@ impl GameInfo{
 		return Ok(());
 	}
 
+	pub fn answer(&self) -> String {
+		self.guess_answer.iter().map(|id| self.letters[*id].as_str()).collect()
+	}
+
+	/// The best color seen so far for every letter of the alphabet, in alphabet order
+	pub fn keyboard(&self) -> String {
+		self.keyboard_status.iter().collect()
+	}
+
+	/// The non-TTY line for the last guess: its colors, a space, then the keyboard status
+	pub fn status_line(&self) -> String {
+		let (_, guess_status) = &self.guess_history[self.guess_history.len()-1];
+		format!("{} {}", guess_status, self.keyboard())
+	}
+
+	/// running, won or lost, as the JSON interfaces report the game
+	pub fn status(&self) -> &'static str {
+		match self.game_status {
+			GameStatus::Running => "running",
+			GameStatus::Success => "won",
+			GameStatus::Fail => "lost",
+		}
+	}
+
+	/// The record of the game for the state file, played with word pack `pack`
+	pub fn to_game(&self, pack: &str) -> Games {
+		let mut game = Games::new();
+		game.set_answer(self.answer());
+		game.set_mode(self.is_difficult, pack);
+		for (guess, _) in &self.guess_history {
+			game.add_guess(guess.clone());
+		}
+		game
+	}
+
+	/// The non-TTY line announcing the end of the game, None while it is running
+	pub fn result_line(&self) -> Option<String> {
+		match self.game_status{
+			GameStatus::Running => None,
+			GameStatus::Success => Some(format!("CORRECT {}", self.guess_history.len())),
+			GameStatus::Fail => Some(format!("FAILED {}", self.answer())),
+		}
+	}
+
 	pub fn print_process(&self, is_tty: bool) -> (){
 		/*
 		If is_tty is true, then will print colorful guess history and keyboard status
@@ -137,28 +214,19 @@ impl GameInfo{
 			utils::clear_command_screen();
 			println!("Guess History:");
 			for (user_input, guess_status) in &self.guess_history{
-				let tmp = user_input.chars().zip(guess_status.chars());
+				let tmp = utils::split_letters(user_input).into_iter().zip(guess_status.chars());
 				for (ch, col) in tmp{
-					utils::print_with_color(ch.to_ascii_uppercase(), col);
+					utils::print_with_color(&ch, col);
 				}
 				print!("\n");
 			}
-			for i in 0_usize..25_usize{
-				let now_char = (('A' as u8) + (i as u8)) as char;
-				utils::print_with_color(now_char.to_ascii_uppercase(), self.keyboard_status[i]);
+			for (letter, status) in self.letters.iter().zip(&self.keyboard_status){
+				utils::print_with_color(letter, *status);
 			}
 			print!("\n");
 		}
 		else{
-			let (_, guess_status) = &self.guess_history[self.guess_history.len()-1];
-			for ch in guess_status.chars(){
-				print!("{}",ch.to_ascii_uppercase());
-			}
-			print!(" ");
-			for ch in self.keyboard_status{
-				print!("{}",ch.to_ascii_uppercase());
-			}
-			print!("\n");
+			println!("{}", self.status_line());
 		}
 		match self.game_status{
 			GameStatus::Running => {
@@ -172,24 +240,16 @@ impl GameInfo{
 					println!("Good Job, You Win!");
 				}
 				else{
-					println!("CORRECT {}", self.guess_history.len());
+					println!("{}", self.result_line().unwrap());
 				}
 			},
 			GameStatus::Fail => {
 				if is_tty {
 					println!("Oh, You've used up all your chances!");
-					print!("The correct answer is: ");
-					for ch in &self.guess_answer{
-						print!("{}", ch.to_ascii_uppercase());
-					}
-					print!("\n");
+					println!("The correct answer is: {}", self.answer());
 				}
 				else{
-					print!("FAILED ");
-					for ch in &self.guess_answer{
-						print!("{}", ch.to_ascii_uppercase());
-					}
-					print!("\n");
+					println!("{}", self.result_line().unwrap());
 				}
 			},
 		}
@@ -197,8 +257,8 @@ impl GameInfo{
 }
 
 pub fn game_runner(answer: &str, is_tty: bool, is_difficult: bool, 
-	stats: &mut Stats, word_dict: &utils::WordDict, game: &mut Games) -> Option<bool> {
-	let mut gameinfo = crate::interact_model::GameInfo::new(answer.trim(), is_difficult);
+	stats: &mut Stats, word_dict: &utils::WordDict, game: &mut Games) -> GameInfo {
+	let mut gameinfo = crate::interact_model::GameInfo::new(answer.trim(), is_difficult, word_dict);
 	if is_tty {
 		println!("Try to Make a Guess!");
 	}
@@ -207,7 +267,7 @@ pub fn game_runner(answer: &str, is_tty: bool, is_difficult: bool,
 		let mut user_guess = String::new();
 		let tmp = io::stdin().read_line(&mut user_guess);
 		assert!(tmp.is_ok());
-		user_guess = String::from(user_guess.trim()).to_uppercase();
+		user_guess = word_dict.alphabet.normalize(&user_guess);
 		let result = gameinfo.make_guess(&user_guess, word_dict);
 		match result{
 			Ok(()) => {
@@ -226,9 +286,5 @@ pub fn game_runner(answer: &str, is_tty: bool, is_difficult: bool,
 		}
 	}
 
-	match gameinfo.game_status {
-		GameStatus::Running => None, 
-		GameStatus::Success => Some(true), 
-		GameStatus::Fail => Some(false),
-	}
+	gameinfo
 }
\ No newline at end of file
@@ -1,27 +1,146 @@
 use serde::{Deserialize, Serialize};
+use chrono::{DateTime, NaiveDate, SecondsFormat};
+use crate::daily;
 use crate::utils::Stats;
-use std::{collections::HashMap};
+use crate::word_pack::DEFAULT_PACK;
+use std::{collections::{BTreeMap, HashMap, HashSet}};
 
-#[derive(Serialize, Deserialize)]
+#[derive(Serialize, Deserialize, Clone)]
 pub struct Gamejson{
 	#[serde(default = "default_total_rounds")]
 	total_rounds: u32, 
 	#[serde(default = "default_games")]
 	games: Vec<Games>,
+	/// Points per player in two-player mode
+	#[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+	match_score: BTreeMap<String, i32>,
+	/// Ids of the achievements earned, in the order they were announced
+	#[serde(default, skip_serializing_if = "Vec::is_empty")]
+	achievements: Vec<String>,
 }
 
 fn default_total_rounds() -> u32{0}
 fn default_games() -> Vec<Games> {Vec::new()}
 
-#[derive(Serialize, Deserialize)]
+#[derive(Serialize, Deserialize, Clone, PartialEq)]
 pub struct Games {
 	answer: String, 
 	guesses: Vec<String>,
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	puzzle: Option<usize>,
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	date: Option<String>,
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	player: Option<String>,
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	setter: Option<String>,
+	#[serde(default, skip_serializing_if = "is_false")]
+	difficult: bool,
+	/// Word pack, only recorded when it is not the default one
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pack: Option<String>,
+	/// When the game was played, RFC 3339. Histories saved before it was recorded lack it.
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	played: Option<String>,
+	/// HMAC chaining this game to the one before, see `signing`
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	signature: Option<String>,
+}
+
+fn is_false(value: &bool) -> bool {
+	!*value
 }
 
 impl Games {
 	pub fn new() -> Games {
-		Games { answer: String::new(), guesses: Vec::new() }
+		let played = daily::now().to_rfc3339_opts(SecondsFormat::Secs, false);
+		Games { answer: String::new(), guesses: Vec::new(), puzzle: None, date: None, player: None, setter: None,
+			difficult: false, pack: None, played: Some(played), signature: None }
+	}
+
+	/// Record who guessed and who chose the answer in two-player mode
+	pub fn set_players(&mut self, player: String, setter: String) {
+		self.player = Some(player);
+		self.setter = Some(setter);
+	}
+
+	/// Mark this game as the daily puzzle `puzzle` of `date`
+	pub fn set_daily(&mut self, puzzle: usize, date: String) {
+		self.puzzle = Some(puzzle);
+		self.date = Some(date);
+	}
+
+	/// Record the difficulty and the word pack the game was played with
+	pub fn set_mode(&mut self, difficult: bool, pack: &str) {
+		self.difficult = difficult;
+		self.pack = if pack == DEFAULT_PACK { None } else { Some(String::from(pack)) };
+	}
+
+	pub fn difficult(&self) -> bool {
+		self.difficult
+	}
+
+	pub fn pack(&self) -> &str {
+		self.pack.as_deref().unwrap_or(DEFAULT_PACK)
+	}
+
+	pub fn signature(&self) -> Option<&str> {
+		self.signature.as_deref()
+	}
+
+	pub fn set_signature(&mut self, signature: Option<String>) {
+		self.signature = signature;
+	}
+
+	/// Whether both records are the same game: everything but the signature matches, the time
+	/// it was played and the player included, so games signed with another key are found too
+	pub fn same_game(&self, other: &Games) -> bool {
+		Games { signature: None, ..self.clone() } == Games { signature: None, ..other.clone() }
+	}
+
+	pub fn answer(&self) -> &str {
+		&self.answer
+	}
+
+	/// The date of a daily puzzle
+	pub fn date(&self) -> Option<&str> {
+		self.date.as_deref()
+	}
+
+	/// When the game was played, RFC 3339
+	pub fn played(&self) -> Option<&str> {
+		self.played.as_deref()
+	}
+
+	/// The day the game was played, or the day of its daily puzzle when the history
+	/// was saved before the time was recorded
+	pub fn played_on(&self) -> Option<NaiveDate> {
+		match &self.played {
+			Some(played) => DateTime::parse_from_rfc3339(played).ok().map(|played| played.date_naive()),
+			None => self.date.as_deref().and_then(|date| daily::parse_date(date).ok()),
+		}
+	}
+
+	pub fn puzzle(&self) -> Option<usize> {
+		self.puzzle
+	}
+
+	/// The guessing player of a two-player game
+	pub fn player(&self) -> Option<&str> {
+		self.player.as_deref()
+	}
+
+	/// How the game was played: daily, two_player or normal
+	pub fn mode(&self) -> &'static str {
+		if self.puzzle.is_some() {
+			"daily"
+		}
+		else if self.player.is_some() {
+			"two_player"
+		}
+		else{
+			"normal"
+		}
 	}
 
 	pub fn set_answer(&mut self, user_input: String) {
@@ -34,7 +153,12 @@ impl Games {
 
 	pub fn check_result(&self) -> bool {
 		// 1: win, 0: lose
-		self.answer == *self.guesses.last().unwrap()
+		self.guesses.last() == Some(&self.answer)
+	}
+
+	/// Keep only the first `len` guesses
+	pub fn truncate_guesses(&mut self, len: usize) {
+		self.guesses.truncate(len);
 	}
 
 	pub fn guesses_number(&self) -> i32 {
@@ -48,7 +172,55 @@ impl Games {
 
 impl Gamejson {
 	pub fn new() -> Gamejson {
-		Gamejson { total_rounds: 0, games: Vec::new() }
+		Gamejson { total_rounds: 0, games: Vec::new(), match_score: BTreeMap::new(), achievements: Vec::new() }
+	}
+
+	pub fn add_points(&mut self, player: &str, points: i32) {
+		*self.match_score.entry(String::from(player)).or_insert(0) += points;
+	}
+
+	pub fn get_points(&self, player: &str) -> i32 {
+		self.match_score.get(player).copied().unwrap_or(0)
+	}
+
+	pub fn achievements(&self) -> &[String] {
+		&self.achievements
+	}
+
+	pub fn add_achievement(&mut self, id: &str) {
+		self.achievements.push(String::from(id));
+	}
+
+	pub fn match_score(&self) -> &BTreeMap<String, i32> {
+		&self.match_score
+	}
+
+	pub fn set_match_score(&mut self, match_score: BTreeMap<String, i32>) {
+		self.match_score = match_score;
+	}
+
+	pub fn games(&self) -> &[Games] {
+		&self.games
+	}
+
+	pub fn games_mut(&mut self) -> &mut [Games] {
+		&mut self.games
+	}
+
+	/// A copy with only the games `keep` accepts
+	pub fn filtered(&self, keep: impl Fn(&Games) -> bool) -> Gamejson {
+		let games: Vec<Games> = self.games.iter().filter(|game| keep(game)).cloned().collect();
+		Gamejson { total_rounds: games.len() as u32, games, match_score: BTreeMap::new(), achievements: Vec::new() }
+	}
+
+	/// The answers played with word pack `pack`
+	pub fn played_answers(&self, pack: &str) -> HashSet<String> {
+		self.games.iter().filter(|game| game.pack() == pack).map(|game| game.answer.clone()).collect()
+	}
+
+	/// Whether the daily puzzle of `date` was played with word pack `pack`
+	pub fn has_daily(&self, pack: &str, date: &str) -> bool {
+		self.games.iter().any(|game| game.puzzle.is_some() && game.date.as_deref() == Some(date) && game.pack() == pack)
 	}
 
 	pub fn get_total(&self) -> i32 {
@@ -80,7 +252,6 @@ impl Gamejson {
 	}
 }
 
-pub fn read_json(raw_json: &str) -> Gamejson {
-	let parsed: Gamejson = serde_json::from_str(raw_json).unwrap();
-	parsed
+pub fn read_json(raw_json: &str) -> Result<Gamejson, serde_json::Error> {
+	serde_json::from_str(raw_json)
 }
\ No newline at end of file
@@ -0,0 +1,182 @@
+// `--protocol jsonl`: one JSON command per stdin line, one JSON object per stdout line
+//
+//   {"command": "new_game"}                     next answer of --seed from --day on
+//   {"command": "new_game", "day": 3}           also "seed", "word" and "difficult"
+//   {"command": "guess", "word": "CRANE"}
+//   {"command": "hint"}                         the solver's pick among the answers left
+//   {"command": "stats"}                        with --sign-key also the "verification", as in `wordle http`
+//   {"command": "quit"}
+//
+// Every reply has "ok"; failed commands carry "error" instead of the result. Replies about
+// a game carry "status" (running, won or lost), "remaining" guesses and the "keyboard",
+// one color per letter of "alphabet". "answer" is only sent once the game is over.
+
+use std::io::{self, BufRead};
+use clap::ValueEnum;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use crate::history_store::HistoryStore;
+use crate::interact_model::{GameInfo, GUESS_CHANCE};
+use crate::json_parser::Gamejson;
+use crate::signing::Counted;
+use crate::shuffle::ShuffleVersion;
+use crate::solver::Solver;
+use crate::utils::WordDict;
+
+#[derive(ValueEnum, Serialize, Deserialize, Clone, Copy, Debug, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum Protocol {
+	/// The terse lines of the non-TTY mode
+	Text,
+	/// JSON commands and replies, one per line
+	Jsonl,
+}
+
+#[derive(Deserialize)]
+#[serde(tag = "command", rename_all = "snake_case", deny_unknown_fields)]
+enum Request {
+	NewGame {
+		word: Option<String>,
+		seed: Option<u64>,
+		day: Option<usize>,
+		difficult: Option<bool>,
+	},
+	Guess {
+		word: String,
+	},
+	Hint,
+	Stats,
+	Quit,
+}
+
+/// Settings from the command line shared by every game of the session
+pub struct Session<'a> {
+	pub word_dict: &'a WordDict,
+	pub seed: u64,
+	pub day: usize,
+	pub shuffle_version: ShuffleVersion,
+	pub difficult: bool,
+	pub pack: String,
+	pub store: Option<Box<dyn HistoryStore>>,
+	/// What the statistics count, see `signing::Counted`
+	pub counted: Counted,
+	pub gamejson: Gamejson,
+}
+
+pub fn run(mut session: Session) -> Result<(), Box<dyn std::error::Error>> {
+	let mut gameinfo: Option<GameInfo> = None;
+	for line in io::stdin().lock().lines() {
+		let line = line?;
+		if line.trim().is_empty() {
+			continue;
+		}
+		let reply = match serde_json::from_str(&line) {
+			Ok(Request::Quit) => {
+				println!("{}", json!({ "ok": true }));
+				break;
+			},
+			Ok(request) => session.handle(request, &mut gameinfo)?,
+			Err(err) => error(&format!("invalid command: {}", err)),
+		};
+		println!("{}", reply);
+	}
+	Ok(())
+}
+
+fn error(message: &str) -> Value {
+	json!({ "ok": false, "error": message })
+}
+
+impl Session<'_> {
+	fn handle(&mut self, request: Request, gameinfo: &mut Option<GameInfo>) -> Result<Value, Box<dyn std::error::Error>> {
+		let word_dict = self.word_dict;
+		let reply = match (request, gameinfo.as_mut()) {
+			(Request::NewGame { word, seed, day, difficult }, _) => {
+				let answer = match (word, day) {
+					(Some(word), _) => Ok(word_dict.alphabet.normalize(&word)),
+					(None, Some(day)) => word_dict.day_answer(seed.unwrap_or(self.seed), day, self.shuffle_version),
+					(None, None) => {
+						self.day += 1;
+						word_dict.day_answer(seed.unwrap_or(self.seed), self.day - 1, self.shuffle_version)
+					},
+				};
+				match answer {
+					Ok(answer) if word_dict.is_final(&answer) => {
+						let new_game = GameInfo::new(&answer, difficult.unwrap_or(self.difficult), word_dict);
+						let mut reply = game_state(&new_game);
+						reply["length"] = json!(word_dict.length);
+						reply["alphabet"] = json!(word_dict.alphabet.letters());
+						*gameinfo = Some(new_game);
+						reply
+					},
+					Ok(_) => error("the answer is not in the final word list"),
+					Err(err) => error(&err.to_string()),
+				}
+			},
+			(Request::Guess { .. } | Request::Hint, None) => error("no game has been started"),
+			(Request::Guess { word }, Some(game)) => {
+				if !game.game_is_running() {
+					error("the game is over")
+				}
+				else if game.make_guess(&word_dict.alphabet.normalize(&word), word_dict).is_err() {
+					let mut reply = game_state(game);
+					reply["ok"] = json!(false);
+					reply["error"] = json!("invalid guess");
+					reply
+				}
+				else{
+					if !game.game_is_running() {
+						self.record(game)?;
+					}
+					let mut reply = game_state(game);
+					let (guess, pattern) = game.guess_history().last().unwrap();
+					reply["guess"] = json!(guess);
+					reply["pattern"] = json!(pattern);
+					reply
+				}
+			},
+			(Request::Hint, Some(game)) if !game.game_is_running() => error("the game is over"),
+			(Request::Hint, Some(game)) => {
+				let mut solver = Solver::new(word_dict);
+				for (guess, pattern) in game.guess_history() {
+					solver.apply(guess, pattern);
+				}
+				match solver.best_guess() {
+					Some(hint) => json!({ "ok": true, "hint": hint, "candidates": solver.candidate_count() }),
+					None => error("no word fits the feedback so far"),
+				}
+			},
+			(Request::Stats, _) => {
+				let mut reply = self.counted.to_json();
+				reply["ok"] = json!(true);
+				reply
+			},
+			(Request::Quit, _) => json!({ "ok": true }),
+		};
+		Ok(reply)
+	}
+
+	/// Add a finished game to the state file
+	fn record(&mut self, gameinfo: &GameInfo) -> Result<(), Box<dyn std::error::Error>> {
+		let game = gameinfo.to_game(&self.pack);
+		self.counted.add_game(game.clone());
+		self.gamejson.add_games(game);
+		if let Some(store) = self.store.as_mut() {
+			store.save(&self.gamejson)?;
+		}
+		Ok(())
+	}
+}
+
+fn game_state(gameinfo: &GameInfo) -> Value {
+	let mut reply = json!({
+		"ok": true,
+		"status": gameinfo.status(),
+		"remaining": GUESS_CHANCE as usize - gameinfo.guess_history().len(),
+		"keyboard": gameinfo.keyboard(),
+	});
+	if !gameinfo.game_is_running() {
+		reply["answer"] = json!(gameinfo.answer());
+	}
+	reply
+}
@@ -0,0 +1,138 @@
+// Letter- and position-level analytics over the saved games: `wordle stats letters`
+//
+// The colors are not saved, every guess is scored again against its answer. Only games of
+// the selected word pack are counted.
+
+use crate::interact_model::score_guess;
+use crate::json_parser::Gamejson;
+use crate::utils::WordDict;
+
+/// Wasted letters listed
+const TOP_WASTED: usize = 10;
+
+#[derive(Default)]
+struct Tally {
+	games: usize,
+	wins: usize,
+	/// Guesses used in the won games
+	attempts: usize,
+}
+
+impl Tally {
+	fn add(&mut self, won: bool, guesses: usize) {
+		self.games += 1;
+		if won {
+			self.wins += 1;
+			self.attempts += guesses;
+		}
+	}
+
+	fn average(&self) -> f64 {
+		match self.wins {
+			0 => 0.0,
+			_ => self.attempts as f64 / self.wins as f64,
+		}
+	}
+}
+
+pub fn run(gamejson: &Gamejson, word_dict: &WordDict, is_tty: bool) {
+	let alphabet = &word_dict.alphabet;
+	let size = alphabet.len();
+	let mut double = Tally::default();
+	let mut single = Tally::default();
+	let mut first_letter: Vec<Tally> = (0..size).map(|_| Tally::default()).collect();
+	let mut wasted = vec![0; size];
+	// per position: the sum over won games of the guess that first made it green
+	let mut green_at = vec![0; word_dict.length];
+	let mut won_games = 0;
+
+	for game in gamejson.games() {
+		let answer = match alphabet.indices(game.answer()) {
+			Some(answer) if answer.len() == word_dict.length => answer,
+			_ => continue,
+		};
+		let guesses: Option<Vec<Vec<usize>>> = game.get_guesses().iter()
+			.map(|guess| alphabet.indices(guess).filter(|guess| guess.len() == answer.len()))
+			.collect();
+		let guesses = match guesses {
+			Some(guesses) => guesses,
+			None => continue,
+		};
+		let won = game.check_result();
+
+		let mut sorted = answer.clone();
+		sorted.sort();
+		sorted.dedup();
+		let tally = if sorted.len() < answer.len() { &mut double } else { &mut single };
+		tally.add(won, guesses.len());
+		first_letter[answer[0]].add(won, guesses.len());
+
+		let mut absent = vec![false; size];
+		let mut first_green = vec![0; answer.len()];
+		for (step, guess) in guesses.iter().enumerate() {
+			let mut counted = vec![false; size];
+			for letter in guess {
+				if absent[*letter] && !counted[*letter] {
+					counted[*letter] = true;
+					wasted[*letter] += 1;
+				}
+			}
+			let pattern: Vec<char> = score_guess(&answer, guess, size).chars().collect();
+			for (position, color) in pattern.iter().enumerate() {
+				if *color == 'G' && first_green[position] == 0 {
+					first_green[position] = step + 1;
+				}
+			}
+			// absent only if no copy of the letter was colored in this guess
+			for (letter, color) in guess.iter().zip(&pattern) {
+				if *color == 'R' && !guess.iter().zip(&pattern).any(|(other, color)| other == letter && *color != 'R') {
+					absent[*letter] = true;
+				}
+			}
+		}
+		if won {
+			won_games += 1;
+			for (position, step) in first_green.iter().enumerate() {
+				green_at[position] += step;
+			}
+		}
+	}
+
+	let mut wasted: Vec<(usize, usize)> = wasted.into_iter().enumerate().filter(|(_, count)| *count > 0).collect();
+	wasted.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(&b.0)));
+	wasted.truncate(TOP_WASTED);
+	let green_at: Vec<f64> = green_at.iter().map(|sum| match won_games {
+		0 => 0.0,
+		_ => *sum as f64 / won_games as f64,
+	}).collect();
+	let letters = alphabet.letters();
+
+	if is_tty {
+		println!("Answers with a double letter: {} games, {} lost", double.games, double.games - double.wins);
+		println!("Answers without: {} games, {} lost", single.games, single.games - single.wins);
+		println!("Average guesses in won games by the first letter of the answer:");
+		for (letter, tally) in first_letter.iter().enumerate().filter(|(_, tally)| tally.games > 0) {
+			println!("  {}: {} games, {:.2}", letters[letter], tally.games, tally.average());
+		}
+		println!("Letters guessed again after they were shown to be absent:");
+		for (letter, count) in &wasted {
+			println!("  {}: {} times", letters[*letter], count);
+		}
+		println!("Average guess that first turned each position green in won games:");
+		for (position, average) in green_at.iter().enumerate() {
+			println!("  {}: {:.2}", position + 1, average);
+		}
+	}
+	else{
+		println!("DOUBLE {} {}", double.games, double.games - double.wins);
+		println!("SINGLE {} {}", single.games, single.games - single.wins);
+		let first: Vec<String> = first_letter.iter().enumerate().filter(|(_, tally)| tally.games > 0)
+			.map(|(letter, tally)| format!("{} {} {:.2}", letters[letter], tally.games, tally.average()))
+			.collect();
+		println!("FIRST {}", first.join(" "));
+		let wasted: Vec<String> = wasted.iter().map(|(letter, count)| format!("{} {}", letters[*letter], count)).collect();
+		println!("WASTED {}", wasted.join(" "));
+		let green_at: Vec<String> = green_at.iter().map(|average| format!("{:.2}", average)).collect();
+		println!("GREEN {}", green_at.join(" "));
+	}
+}
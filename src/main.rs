@@ -1,14 +1,34 @@
 use console;
-use std::{io::{self, Write}, fs::{self, read_to_string}};
-use clap::Parser;
-use rand::{seq::SliceRandom, SeedableRng};
+use std::{io::{self, Write}, fs::{self, read_to_string}, collections::HashSet};
+use clap::{Parser, Subcommand};
 use serde::{Deserialize, Serialize};
 
 pub mod interact_model;
 mod utils;
 mod builtin_words;
 mod json_parser;
-use json_parser::{Gamejson, Games, read_json};
+mod dict_command;
+mod word_pack;
+mod daily;
+mod shuffle;
+mod share;
+mod challenge;
+mod solver;
+mod reverse_mode;
+mod hotseat;
+mod race_server;
+mod http_server;
+mod jsonl_protocol;
+mod arena;
+mod replay;
+mod analysis;
+mod stats_command;
+mod letter_stats;
+mod achievements;
+mod history_store;
+mod state_command;
+mod signing;
+use json_parser::Games;
 
 #[derive(Parser, Debug)]
 #[derive(Serialize, Deserialize)]
@@ -38,11 +58,133 @@ struct Args{
     #[arg(short, long)]
     acceptable_set: Option<String>,
 
-    #[arg(short='S', long)]
+    /// Game history: a JSON file, or jsonl:ADDRESS for an append-only log, sqlite:ADDRESS for a database
+    #[arg(short='S', long, global = true)]
     state: Option<String>,
 
+    /// Sign the games saved to --state with the key in this file. Stats then count only the
+    /// signed games that verify, and state merge and repair sign their changes with it.
+    #[arg(long, global = true)]
+    sign_key: Option<String>,
+
     #[arg(short, long)]
     config: Option<String>,
+
+    #[arg(short, long)]
+    pack: Option<String>,
+
+    #[arg(long)]
+    pack_dir: Option<String>,
+
+    /// Algorithm mapping a seed to the answer order, see src/shuffle.rs
+    #[arg(long)]
+    shuffle_version: Option<u32>,
+
+    /// In random mode, skip answers that already appear in the state file
+    #[arg(long, default_value_t = false)]
+    #[serde(default)]
+    no_repeat: bool,
+
+    /// Play today's puzzle, numbered by days since the epoch
+    #[arg(long, default_value_t = false)]
+    #[serde(default)]
+    daily: bool,
+
+    /// Day of daily puzzle #0, YYYY-MM-DD
+    #[arg(long)]
+    epoch: Option<String>,
+
+    /// Play the daily puzzle of this date instead of today, YYYY-MM-DD
+    #[arg(long)]
+    date: Option<String>,
+
+    /// Play the word hidden in a challenge code
+    #[arg(long)]
+    challenge: Option<String>,
+
+    /// Think of a word and let the program guess it
+    #[arg(long, default_value_t = false)]
+    #[serde(default)]
+    reverse: bool,
+
+    /// Two players take turns choosing the answer for each other
+    #[arg(long, default_value_t = false)]
+    #[serde(default)]
+    two_player: bool,
+
+    /// Names of the two players, comma separated
+    #[arg(long)]
+    players: Option<String>,
+
+    /// Print the shareable emoji grid after each game
+    #[arg(long, default_value_t = false)]
+    #[serde(default)]
+    share: bool,
+
+    /// Use orange and blue squares in the shareable grid
+    #[arg(long, default_value_t = false)]
+    #[serde(default)]
+    high_contrast: bool,
+
+    /// Also write the shareable grid to this file
+    #[arg(long)]
+    share_file: Option<String>,
+
+    /// Number of favorite words listed with --stats
+    #[arg(long)]
+    top: Option<usize>,
+
+    /// In a terminal, show the skill and luck of every guess after each game
+    #[arg(long, default_value_t = false)]
+    #[serde(default)]
+    analyze: bool,
+
+    /// Keep track of achievements in the state file and announce new ones in a terminal
+    #[arg(long, default_value_t = false)]
+    #[serde(default)]
+    achievements: bool,
+
+    /// Format of the input and output, jsonl for one JSON command and reply per line, in a terminal too
+    #[arg(long, value_enum)]
+    protocol: Option<jsonl_protocol::Protocol>,
+
+    #[command(subcommand)]
+    #[serde(skip)]
+    command: Option<Command>,
+}
+
+#[derive(Subcommand, Debug, Clone)]
+enum Command {
+    /// Inspect and maintain word lists
+    Dict {
+        #[command(subcommand)]
+        action: dict_command::DictAction,
+    },
+    /// Create codes that let a friend play a word of your choice
+    Challenge {
+        #[command(subcommand)]
+        action: challenge::ChallengeAction,
+    },
+    /// Host a race on a local TCP port, everyone guesses the same answer
+    Serve(race_server::ServeArgs),
+    /// Host games behind a JSON API over HTTP
+    Http(http_server::HttpArgs),
+    /// Let external solver programs play the same answers and rank them
+    Arena(arena::ArenaArgs),
+    /// Show a saved game from the state file again
+    Replay(replay::ReplayArgs),
+    /// Break a saved game down into the skill and luck of each guess
+    Analyze(analysis::AnalyzeArgs),
+    /// Reports on the games of the state file
+    Stats {
+        #[command(subcommand)]
+        action: stats_command::StatsAction,
+    },
+    /// Maintenance of the state file
+    State {
+        #[command(subcommand)]
+        action: state_command::StateAction,
+    },
 }
 
 impl Args{
@@ -55,6 +197,22 @@ impl Args{
                 return true;
             }
         }
+        if self.daily && (self.word.is_some() || self.random || self.day.is_some()) {
+            return true;
+        }
+        if self.challenge.is_some() && (self.word.is_some() || self.random || self.daily || self.day.is_some()) {
+            return true;
+        }
+        if self.protocol == Some(jsonl_protocol::Protocol::Jsonl) && (self.word.is_some() || self.random || self.daily
+            || self.challenge.is_some() || self.reverse || self.two_player) {
+            return true;
+        }
+        if self.reverse && (self.word.is_some() || self.random || self.daily || self.challenge.is_some()) {
+            return true;
+        }
+        if self.two_player && (self.word.is_some() || self.random || self.daily || self.challenge.is_some() || self.reverse) {
+            return true;
+        }
         false
     }
 
@@ -86,10 +244,82 @@ impl Args{
         if self.state.is_none(){
             self.state = from_json.state;
         }
+        if self.sign_key.is_none(){
+            self.sign_key = from_json.sign_key;
+        }
         if self.config.is_none(){
             self.config = from_json.config;
         }
+        if self.pack.is_none(){
+            self.pack = from_json.pack;
+        }
+        if self.pack_dir.is_none(){
+            self.pack_dir = from_json.pack_dir;
+        }
+        if self.shuffle_version.is_none(){
+            self.shuffle_version = from_json.shuffle_version;
+        }
+        if !self.no_repeat{
+            self.no_repeat = from_json.no_repeat;
+        }
+        if !self.daily{
+            self.daily = from_json.daily;
+        }
+        if self.epoch.is_none(){
+            self.epoch = from_json.epoch;
+        }
+        if self.date.is_none(){
+            self.date = from_json.date;
+        }
+        if self.challenge.is_none(){
+            self.challenge = from_json.challenge;
+        }
+        if !self.reverse{
+            self.reverse = from_json.reverse;
+        }
+        if !self.two_player{
+            self.two_player = from_json.two_player;
+        }
+        if self.players.is_none(){
+            self.players = from_json.players;
+        }
+        if !self.share{
+            self.share = from_json.share;
+        }
+        if !self.high_contrast{
+            self.high_contrast = from_json.high_contrast;
+        }
+        if self.share_file.is_none(){
+            self.share_file = from_json.share_file;
+        }
+        if self.top.is_none(){
+            self.top = from_json.top;
+        }
+        if !self.analyze{
+            self.analyze = from_json.analyze;
+        }
+        if !self.achievements{
+            self.achievements = from_json.achievements;
+        }
+        if self.protocol.is_none(){
+            self.protocol = from_json.protocol;
+        }
+    }
+}
+
+/// The answer of a hosted game: `--word`, or the `--day` answer of `--seed`
+fn hosted_answer(args: &Args, word_dict: &utils::WordDict) -> Result<String, Box<dyn std::error::Error>> {
+    let answer = match &args.word {
+        Some(word) => word_dict.alphabet.normalize(word),
+        None => {
+            let shuffle_version = shuffle::ShuffleVersion::from_number(args.shuffle_version.unwrap_or(1))?;
+            word_dict.day_answer(args.seed.unwrap_or(shuffle::DEFAULT_SEED), args.day.unwrap_or(1), shuffle_version)?
+        },
+    };
+    if !word_dict.is_final(&answer) {
+        return Err("the answer is not in the final word list".into());
     }
+    Ok(answer)
 }
 
 /// The main function for the Wordle game, implement your own logic here
@@ -108,28 +338,126 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
         return Err("Args are conflict".into());
     }
 
+    let challenge = match &args.challenge {
+        Some(code) => Some(challenge::decode(code)?),
+        None => None,
+    };
+    if let Some(challenge) = &challenge {
+        // the code decides the pack, and asks for difficult mode if it was created in it
+        if args.pack.as_ref().is_some_and(|pack| *pack != challenge.pack) {
+            return Err(format!("the challenge is for word pack {}", challenge.pack).into());
+        }
+        args.pack = Some(challenge.pack.clone());
+        args.difficult |= challenge.difficult;
+    }
+
+    let pack_dir = word_pack::pack_dir(args.pack_dir.clone());
+    let pack = word_pack::find(args.pack.as_deref().unwrap_or(word_pack::DEFAULT_PACK), pack_dir.as_deref())?;
+
+    if let Some(command) = args.command.take() {
+        return match command {
+            Command::Dict { action } => dict_command::run(action, &pack, args.final_set, args.acceptable_set, pack_dir.as_deref()),
+            Command::Challenge { action } => {
+                let mut word_dict = utils::WordDict::new();
+                word_dict.build(&pack, args.final_set.clone(), args.acceptable_set.clone())?;
+                challenge::run(action, &word_dict, &pack.name, args.difficult)
+            },
+            Command::Serve(serve_args) => {
+                let mut word_dict = utils::WordDict::new();
+                word_dict.build(&pack, args.final_set.clone(), args.acceptable_set.clone())?;
+                let answer = hosted_answer(&args, &word_dict)?;
+                race_server::run(serve_args, &answer, args.difficult, &word_dict, atty::is(atty::Stream::Stdout))
+            },
+            Command::Http(http_args) => {
+                let mut word_dict = utils::WordDict::new();
+                word_dict.build(&pack, args.final_set.clone(), args.acceptable_set.clone())?;
+                let (store, gamejson) = history_store::load(args.state.as_deref(), args.sign_key.as_deref())?;
+                let host = http_server::Host {
+                    word_dict: &word_dict,
+                    shuffle_version: shuffle::ShuffleVersion::from_number(args.shuffle_version.unwrap_or(1))?,
+                    difficult: args.difficult,
+                    pack: pack.name.clone(),
+                    store,
+                    counted: signing::Counted::new(args.sign_key.as_deref(), &gamejson)?,
+                    gamejson,
+                };
+                http_server::run(http_args, host, atty::is(atty::Stream::Stdout))
+            },
+            Command::Arena(arena_args) => {
+                let mut word_dict = utils::WordDict::new();
+                word_dict.build(&pack, args.final_set.clone(), args.acceptable_set.clone())?;
+                let shuffle_version = shuffle::ShuffleVersion::from_number(args.shuffle_version.unwrap_or(1))?;
+                arena::run(arena_args, &word_dict, args.seed.unwrap_or(shuffle::DEFAULT_SEED), args.day.unwrap_or(1),
+                    shuffle_version, args.difficult, atty::is(atty::Stream::Stdout))
+            },
+            Command::Replay(replay_args) => {
+                let mut word_dict = utils::WordDict::new();
+                word_dict.build(&pack, args.final_set.clone(), args.acceptable_set.clone())?;
+                let state = args.state.as_ref().ok_or("replay needs the state file, pass it with --state")?;
+                let gamejson = history_store::open(state)?.load()?;
+                replay::run(replay_args, &gamejson, &word_dict, atty::is(atty::Stream::Stdout))
+            },
+            Command::Analyze(analyze_args) => {
+                let mut word_dict = utils::WordDict::new();
+                word_dict.build(&pack, args.final_set.clone(), args.acceptable_set.clone())?;
+                let state = args.state.as_ref().ok_or("analyze needs the state file, pass it with --state")?;
+                let gamejson = history_store::open(state)?.load()?;
+                analysis::run(analyze_args, &gamejson, &word_dict, atty::is(atty::Stream::Stdout))
+            },
+            Command::Stats { action } => {
+                let mut word_dict = utils::WordDict::new();
+                word_dict.build(&pack, args.final_set.clone(), args.acceptable_set.clone())?;
+                let state = args.state.as_ref().ok_or("stats needs the state file, pass it with --state")?;
+                let gamejson = history_store::open(state)?.load()?;
+                let counted = signing::Counted::new(args.sign_key.as_deref(), &gamejson)?;
+                if let Some(verification) = counted.verification() {
+                    signing::print_verification(verification, atty::is(atty::Stream::Stdout));
+                }
+                stats_command::run(action, counted.gamejson(), &word_dict, &pack.name, atty::is(atty::Stream::Stdout))
+            },
+            Command::State { action } => {
+                let mut word_dict = utils::WordDict::new();
+                word_dict.build(&pack, args.final_set.clone(), args.acceptable_set.clone())?;
+                state_command::run(action, args.state.as_deref(), args.sign_key.as_deref(), &word_dict, &pack.name, atty::is(atty::Stream::Stdout))
+            },
+        };
+    }
+
     // eprintln!("Args: {}", args.acceptable_set.clone().unwrap_or(String::from("None")));
 
     let mut word_dict = utils::WordDict::new();
-    word_dict.build(args.final_set, args.acceptable_set)?;
-
-    let mut gamejson = if args.state.is_some(){
-        let raw_json = read_to_string(args.state.as_ref().unwrap());
-        if raw_json.is_ok(){
-            read_json(&raw_json.unwrap())
-        }
-        else{
-            Gamejson::new()
+    word_dict.build(&pack, args.final_set, args.acceptable_set)?;
+    if let Some(challenge) = &challenge {
+        challenge::check_word(&challenge.word, &word_dict)?;
+        if challenge.length != word_dict.length {
+            return Err("the challenge code does not match the word length of the pack".into());
         }
     }
-    else{
-        Gamejson::new()
-    };
 
-    let mut rng = rand::rngs::StdRng::seed_from_u64(args.seed.unwrap_or(114514 as u64));
+    let (mut store, mut gamejson) = history_store::load(args.state.as_deref(), args.sign_key.as_deref())?;
+
+    let shuffle_version = shuffle::ShuffleVersion::from_number(args.shuffle_version.unwrap_or(1))?;
 
     let is_tty = atty::is(atty::Stream::Stdout);
 
+    if args.reverse {
+        return reverse_mode::run(&word_dict, is_tty);
+    }
+
+    if args.protocol == Some(jsonl_protocol::Protocol::Jsonl) {
+        return jsonl_protocol::run(jsonl_protocol::Session {
+            word_dict: &word_dict,
+            seed: args.seed.unwrap_or(shuffle::DEFAULT_SEED),
+            day: args.day.unwrap_or(1),
+            shuffle_version,
+            difficult: args.difficult,
+            pack: pack.name.clone(),
+            store,
+            counted: signing::Counted::new(args.sign_key.as_deref(), &gamejson)?,
+            gamejson,
+        });
+    }
+
     if is_tty {
         utils::clear_command_screen();
         print!("{}", console::style("Your name: ").bold().red());
@@ -140,28 +468,71 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
     }
 
     let len = word_dict.final_list.len();
-    let mut select_order = vec![0_usize; len];
-    for i in 0..len {
-        select_order[i] = i;
-    }
-    select_order.shuffle(&mut rng);
+    let select_order = shuffle::answer_order(len, args.seed.unwrap_or(shuffle::DEFAULT_SEED), shuffle_version);
     let mut idx = args.day.unwrap_or(1)-1;
 
+    let daily = if args.daily {
+        let date = match &args.date {
+            Some(date) => daily::parse_date(date)?,
+            None => daily::today(),
+        };
+        let epoch = daily::parse_date(args.epoch.as_deref().unwrap_or(daily::DEFAULT_EPOCH))?;
+        let puzzle = daily::puzzle_number(date, epoch)?;
+        if gamejson.has_daily(&pack.name, &date.to_string()) {
+            return Err(format!("daily puzzle #{} has already been played", puzzle).into());
+        }
+        Some((puzzle, date))
+    }
+    else{
+        None
+    };
+
+    let players: Vec<String> = args.players.as_deref().unwrap_or("Player 1,Player 2")
+        .split(',').map(|player| String::from(player.trim())).collect();
+    if args.two_player && (players.len() != 2 || players[0] == players[1]) {
+        return Err("--players needs two different names separated by a comma".into());
+    }
+    let mut round = 0;
+
+    let mut played = if args.no_repeat { gamejson.played_answers(&pack.name) } else { HashSet::new() };
+
     // let mut stats = Stats::new(None, None, None, None, None);
-    let mut stats = gamejson.to_stats();
+    let counted = signing::Counted::new(args.sign_key.as_deref(), &gamejson)?;
+    if let (true, Some(verification)) = (args.stats, counted.verification()) {
+        signing::print_verification(verification, is_tty);
+    }
+    let mut stats = counted.gamejson().to_stats();
+    stats.set_top(args.top.unwrap_or(utils::DEFAULT_TOP));
 
     loop {
         let mut answer = String::new();
-        if args.random {
+        if let Some((puzzle, _)) = daily {
+            // the puzzle number keeps growing, wrap around the answer list
+            answer = word_dict.final_list[select_order[puzzle % len]].clone();
+        }
+        else if args.random {
             // random 
-            assert!(idx < select_order.len());
-            answer = word_dict.final_list[select_order[idx]].clone();
-            idx += 1;
+            match word_dict.next_answer(&select_order, idx, &played) {
+                Some((word, next_idx)) => {
+                    answer = word;
+                    idx = next_idx;
+                },
+                None if args.no_repeat => {
+                    return Err(format!("every answer from day {} on has already been played", args.day.unwrap_or(1)).into());
+                },
+                None => return Err(format!("day {} is past the end of the {} answers", idx + 1, len).into()),
+            }
+        }
+        else if let Some(challenge) = &challenge {
+            answer = challenge.word.clone();
         }
         else if args.word.is_some() {
             // read from args
             answer = args.word.clone().unwrap();
         }
+        else if args.two_player {
+            answer = hotseat::read_secret_word(&players[round % 2], &players[(round + 1) % 2], &word_dict, is_tty)?;
+        }
         else{
             if is_tty {
                 println!("Please setting the answer:");
@@ -170,26 +541,78 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
             io::stdin().read_line(&mut answer)?;
         }
 
-        answer = answer.to_uppercase();
+        answer = word_dict.alphabet.normalize(&answer);
+        match word_dict.alphabet.indices(&answer) {
+            Some(letters) if letters.len() == word_dict.length => (),
+            _ => return Err(format!("answer {} is not a {}-letter word of the alphabet", answer, word_dict.length).into()),
+        }
+
+        if args.no_repeat {
+            played.insert(answer.clone());
+        }
 
         let mut game = Games::new();
         game.set_answer(answer.clone());
+        game.set_mode(args.difficult, &pack.name);
+        if let Some((puzzle, date)) = daily {
+            game.set_daily(puzzle, date.to_string());
+        }
 
-        let result = interact_model::game_runner(
+        let gameinfo = interact_model::game_runner(
             &answer, is_tty, args.difficult, &mut stats, &word_dict, &mut game);
-        stats.add_game(result.unwrap());
+        stats.add_game(gameinfo.result().unwrap());
+        if args.two_player {
+            let (setter, guesser) = (&players[round % 2], &players[(round + 1) % 2]);
+            game.set_players(guesser.clone(), setter.clone());
+            hotseat::score_round(&mut gamejson, guesser, setter, gameinfo.result() == Some(true));
+        }
         gamejson.add_games(game);
-        if args.state.is_some() {
-            // save args to file
-            let json = serde_json::to_string(&gamejson).unwrap();
-            fs::write(args.state.as_ref().unwrap(), json)?;
+        let unlocked = if args.achievements { achievements::unlock(&mut gamejson) } else { Vec::new() };
+        if let Some(store) = store.as_mut() {
+            store.save(&gamejson)?;
+        }
+
+        if let Some((puzzle, _)) = daily {
+            if is_tty {
+                println!("Daily puzzle #{}", puzzle);
+            }
+            else{
+                println!("DAILY {}", puzzle);
+            }
+        }
+
+        if args.two_player {
+            hotseat::print_score(&gamejson, &players, is_tty);
+        }
+
+        if is_tty {
+            achievements::announce(&unlocked);
+        }
+
+        if args.analyze && is_tty {
+            analysis::print_analysis(&analysis::analyze(gameinfo.guess_history(), &word_dict), is_tty);
+        }
+
+        if args.share || args.share_file.is_some() {
+            let puzzle = match daily {
+                Some((puzzle, _)) => Some(puzzle.to_string()),
+                None if args.random => Some(format!("#{}", idx)),
+                None => None,
+            };
+            let text = share::share_text(&gameinfo, puzzle.as_deref(), args.high_contrast);
+            if args.share {
+                print!("{}", text);
+            }
+            if let Some(address) = &args.share_file {
+                fs::write(address, &text)?;
+            }
         }
 
         if args.stats{
             stats.print_result(is_tty);
         }
 
-        if args.word.is_some() {
+        if args.word.is_some() || daily.is_some() || challenge.is_some() {
             break;
         }
         
@@ -204,6 +627,7 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
         if is_tty{
             utils::clear_command_screen();
         }
+        round += 1;
     }
     Ok(())
 }
@@ -0,0 +1,161 @@
+// `wordle serve`: a race on a local TCP port where every client guesses the same answer
+//
+// Line protocol, one message per line:
+//   client -> server   the player's name, then one guess per line
+//   server -> client   "WELCOME <name>" when connected, or "TAKEN <name>" before closing
+//                      the connection if another player already has the name, "START <letters> <chances>" once
+//                      every player has joined, then for each guess the same lines as the
+//                      non-TTY mode: "GYRRR XXXX...", "INVALID", and "CORRECT n" or
+//                      "FAILED ANSWER" when the game ends, followed by "RANK k"
+
+use std::io::{BufRead, BufReader, Write};
+use std::net::{TcpListener, TcpStream};
+use std::sync::mpsc;
+use std::sync::Mutex;
+use std::thread;
+use std::time::Duration;
+use clap::Args;
+use crate::interact_model::{GameInfo, GUESS_CHANCE};
+use crate::utils::WordDict;
+
+#[derive(Args, Debug, Clone)]
+pub struct ServeArgs {
+	/// Port to listen on, 0 picks a free one
+	#[arg(long, default_value_t = 4242)]
+	port: u16,
+
+	/// Address to bind to
+	#[arg(long, default_value = "127.0.0.1")]
+	bind: String,
+
+	/// Number of players to wait for before the race starts
+	#[arg(long, default_value_t = 2)]
+	players: usize,
+}
+
+/// How long a new connection may take to send its name
+const NAME_TIMEOUT: Duration = Duration::from_secs(30);
+
+struct Finisher {
+	name: String,
+	solved: bool,
+	guesses: usize,
+}
+
+pub fn run(serve_args: ServeArgs, answer: &str, is_difficult: bool, word_dict: &WordDict, is_tty: bool) -> Result<(), Box<dyn std::error::Error>> {
+	let listener = TcpListener::bind((serve_args.bind.as_str(), serve_args.port))?;
+	let address = listener.local_addr()?;
+	if is_tty {
+		println!("Race server listening on {}, waiting for {} players", address, serve_args.players);
+	}
+	else{
+		println!("LISTENING {}", address);
+	}
+
+	// names are read on a thread per connection, so a silent client does not hold up the lobby
+	let (sender, arrivals) = mpsc::channel();
+	thread::spawn(move || {
+		for stream in listener.incoming().map_while(Result::ok) {
+			let sender = sender.clone();
+			thread::spawn(move || {
+				if let Some(arrival) = read_name(stream) {
+					let _ = sender.send(arrival);
+				}
+			});
+		}
+	});
+
+	let mut clients: Vec<(String, BufReader<TcpStream>, TcpStream)> = Vec::new();
+	while clients.len() < serve_args.players {
+		let (name, reader, stream) = arrivals.recv()?;
+		if clients.iter().any(|(taken, _, _)| *taken == name) {
+			let _ = writeln!(&stream, "TAKEN {}", name);
+			continue;
+		}
+		writeln!(&stream, "WELCOME {}", name)?;
+		if is_tty {
+			println!("{} joined ({}/{})", name, clients.len() + 1, serve_args.players);
+		}
+		else{
+			println!("JOINED {}", name);
+		}
+		clients.push((name, reader, stream));
+	}
+
+	let scoreboard: Mutex<Vec<Finisher>> = Mutex::new(Vec::new());
+	thread::scope(|scope| {
+		for (name, reader, stream) in clients {
+			let scoreboard = &scoreboard;
+			scope.spawn(move || {
+				let _ = writeln!(&stream, "START {} {}", word_dict.length, GUESS_CHANCE);
+				let mut gameinfo = GameInfo::new(answer, is_difficult, word_dict);
+				let result = play(&mut gameinfo, reader, &stream, word_dict);
+				let mut scoreboard = scoreboard.lock().unwrap();
+				scoreboard.push(Finisher { name, solved: result.unwrap_or(false), guesses: gameinfo.guess_history().len() });
+				let rank = ranking(&scoreboard).iter().position(|id| *id == scoreboard.len() - 1).unwrap();
+				let _ = writeln!(&stream, "RANK {}", rank + 1);
+				print_scoreboard(&scoreboard, is_tty);
+			});
+		}
+	});
+	Ok(())
+}
+
+/// The name a new client sends first, None if it sends none within `NAME_TIMEOUT`
+fn read_name(stream: TcpStream) -> Option<(String, BufReader<TcpStream>, TcpStream)> {
+	stream.set_read_timeout(Some(NAME_TIMEOUT)).ok()?;
+	let mut reader = BufReader::new(stream.try_clone().ok()?);
+	let mut name = String::new();
+	if reader.read_line(&mut name).ok()? == 0 || name.trim().is_empty() {
+		return None;
+	}
+	stream.set_read_timeout(None).ok()?;
+	Some((String::from(name.trim()), reader, stream))
+}
+
+/// Play one client's game, Some(solved) once it ends, None if the client leaves early
+fn play(gameinfo: &mut GameInfo, mut reader: BufReader<TcpStream>, stream: &TcpStream, word_dict: &WordDict) -> Option<bool> {
+	while gameinfo.game_is_running() {
+		let mut line = String::new();
+		match reader.read_line(&mut line) {
+			Ok(0) | Err(_) => return None,
+			Ok(_) => (),
+		}
+		let guess = word_dict.alphabet.normalize(&line);
+		let reply = match gameinfo.make_guess(&guess, word_dict) {
+			Ok(()) => match gameinfo.result_line() {
+				Some(result) => format!("{}\n{}", gameinfo.status_line(), result),
+				None => gameinfo.status_line(),
+			},
+			Err(()) => String::from("INVALID"),
+		};
+		writeln!(&*stream, "{}", reply).ok()?;
+	}
+	gameinfo.result()
+}
+
+/// Indices into `scoreboard`: solvers first, ordered by who finished first, then everyone
+/// who failed or left.
+fn ranking(scoreboard: &[Finisher]) -> Vec<usize> {
+	let mut rows: Vec<usize> = (0..scoreboard.len()).filter(|id| scoreboard[*id].solved).collect();
+	rows.extend((0..scoreboard.len()).filter(|id| !scoreboard[*id].solved));
+	rows
+}
+
+fn print_scoreboard(scoreboard: &[Finisher], is_tty: bool) {
+	if is_tty {
+		println!("Scoreboard:");
+	}
+	else{
+		println!("SCOREBOARD");
+	}
+	for (rank, id) in ranking(scoreboard).into_iter().enumerate() {
+		let finisher = &scoreboard[id];
+		match (finisher.solved, is_tty) {
+			(true, true) => println!("{}. {} solved it in {} guesses", rank + 1, finisher.name, finisher.guesses),
+			(false, true) => println!("{}. {} did not solve it", rank + 1, finisher.name),
+			(true, false) => println!("{} {} CORRECT {}", rank + 1, finisher.name, finisher.guesses),
+			(false, false) => println!("{} {} FAILED", rank + 1, finisher.name),
+		}
+	}
+}
@@ -0,0 +1,119 @@
+// `wordle replay`: show a game from the state file again, one guess at a time
+
+use std::io;
+use clap::Args;
+use crate::daily;
+use crate::interact_model::GameInfo;
+use crate::json_parser::{Gamejson, Games};
+use crate::solver::Solver;
+use crate::utils::{self, WordDict};
+
+/// Which game of the state file to look at
+#[derive(Args, Debug, Clone)]
+pub struct GameSelector {
+	/// Position of the game in the state file, from 1; the last game by default
+	#[arg(long, conflicts_with_all = ["date", "answer"])]
+	index: Option<usize>,
+
+	/// Date of the daily puzzle to show, YYYY-MM-DD
+	#[arg(long, conflicts_with = "answer")]
+	date: Option<String>,
+
+	/// Show the latest game with this answer
+	#[arg(long)]
+	answer: Option<String>,
+}
+
+#[derive(Args, Debug, Clone)]
+pub struct ReplayArgs {
+	#[command(flatten)]
+	game: GameSelector,
+
+	/// Before each guess, show how many answers were still possible and what the solver would play
+	#[arg(long, default_value_t = false)]
+	annotate: bool,
+}
+
+pub fn run(replay_args: ReplayArgs, gamejson: &Gamejson, word_dict: &WordDict, is_tty: bool) -> Result<(), Box<dyn std::error::Error>> {
+	let (number, game) = select(&replay_args.game, gamejson, word_dict)?;
+	let gameinfo = rescore(number, game, word_dict)?;
+
+	if is_tty {
+		println!("Game #{}, press Enter for the next guess", number);
+	}
+	else{
+		println!("GAME {}", number);
+	}
+	let mut solver = Solver::new(word_dict);
+	for (step, (guess, pattern)) in gameinfo.guess_history().iter().enumerate() {
+		if is_tty && step > 0 {
+			io::stdin().read_line(&mut String::new())?;
+		}
+		let annotation = match replay_args.annotate {
+			true => Some((solver.candidate_count(), solver.best_guess().unwrap_or_default())),
+			false => None,
+		};
+		solver.apply(guess, pattern);
+		if is_tty {
+			for (letter, color) in utils::split_letters(guess).into_iter().zip(pattern.chars()) {
+				utils::print_with_color(&letter, color);
+			}
+			match annotation {
+				Some((candidates, best)) => println!("  {} possible answers, the solver would play {}", candidates, best),
+				None => println!(),
+			}
+		}
+		else{
+			match annotation {
+				Some((candidates, best)) => println!("{} {} {} {}", guess, pattern, candidates, best),
+				None => println!("{} {}", guess, pattern),
+			}
+		}
+	}
+
+	match (gameinfo.result(), is_tty) {
+		(Some(true), true) => println!("Solved in {} guesses", gameinfo.guess_history().len()),
+		(_, true) => println!("Not solved, the answer was {}", gameinfo.answer()),
+		(_, false) => println!("{}", gameinfo.result_line().unwrap_or_else(|| format!("UNFINISHED {}", gameinfo.answer()))),
+	}
+	Ok(())
+}
+
+/// The chosen game and its position in the state file, from 1
+pub fn select<'a>(selector: &GameSelector, gamejson: &'a Gamejson, word_dict: &WordDict) -> Result<(usize, &'a Games), Box<dyn std::error::Error>> {
+	let games = gamejson.games();
+	let found = match (&selector.index, &selector.date, &selector.answer) {
+		(Some(index), _, _) => {
+			let game = index.checked_sub(1).and_then(|id| games.get(id));
+			return game.map(|game| (*index, game))
+				.ok_or_else(|| format!("there is no game #{} in the state file ({} games)", index, games.len()).into());
+		},
+		(None, Some(date), _) => {
+			let date = daily::parse_date(date)?.to_string();
+			games.iter().enumerate().rev().find(|(_, game)| game.date() == Some(date.as_str()))
+				.ok_or_else(|| format!("no daily puzzle of {} in the state file", date))?
+		},
+		(None, None, Some(answer)) => {
+			let answer = word_dict.alphabet.normalize(answer);
+			games.iter().enumerate().rev().find(|(_, game)| game.answer() == answer)
+				.ok_or_else(|| format!("no game with the answer {} in the state file", answer))?
+		},
+		(None, None, None) => games.iter().enumerate().next_back().ok_or("the state file has no games")?,
+	};
+	Ok((found.0 + 1, found.1))
+}
+
+/// Play the saved guesses again to get their colors
+pub fn rescore(number: usize, game: &Games, word_dict: &WordDict) -> Result<GameInfo, Box<dyn std::error::Error>> {
+	match word_dict.alphabet.indices(game.answer()) {
+		Some(letters) if letters.len() == word_dict.length => (),
+		_ => return Err(format!("game #{} does not fit the selected word pack", number).into()),
+	}
+	let mut gameinfo = GameInfo::new(game.answer(), false, word_dict);
+	for guess in game.get_guesses() {
+		if gameinfo.make_guess(&guess, word_dict).is_err() {
+			return Err(format!("guess {} of game #{} is not in the word list", guess, number).into());
+		}
+	}
+	Ok(gameinfo)
+}
@@ -0,0 +1,95 @@
+// Reverse mode: the player thinks of a word and the program guesses it
+
+use std::io;
+use crate::solver::Solver;
+use crate::utils::{self, WordDict};
+
+pub fn run(word_dict: &WordDict, is_tty: bool) -> Result<(), Box<dyn std::error::Error>> {
+	if is_tty {
+		println!("Think of a word from the answer list, I will try to guess it.");
+		println!("Reply to every guess with one letter per position: G (right place), Y (wrong place), R (not in the word).");
+	}
+	let mut solver = Solver::new(word_dict);
+	let mut history: Vec<(String, String)> = Vec::new();
+	loop {
+		let guess = match solver.best_guess() {
+			Some(guess) => guess,
+			None => return Err("no word in the answer list matches the feedback".into()),
+		};
+		if is_tty {
+			println!("Guess {}: {}", history.len() + 1, guess);
+		}
+		else{
+			println!("{}", guess);
+		}
+
+		let pattern = loop {
+			let mut line = String::new();
+			if io::stdin().read_line(&mut line)? == 0 {
+				return Err("input ended before the word was found".into());
+			}
+			let pattern = line.trim().to_uppercase();
+			match check_pattern(&guess, &pattern, word_dict) {
+				Ok(()) => break pattern,
+				Err(reason) => {
+					if is_tty {
+						println!("{}, please re-enter the feedback for {}:", reason, guess);
+					}
+					else{
+						println!("INVALID");
+					}
+				},
+			}
+		};
+
+		let before = solver.candidate_count();
+		solver.apply(&guess, &pattern);
+		history.push((guess.clone(), pattern.clone()));
+		if pattern.chars().all(|col| col == 'G') {
+			if is_tty {
+				println!("Got it! I needed {} guesses.", history.len());
+			}
+			else{
+				println!("SOLVED {}", history.len());
+			}
+			return Ok(());
+		}
+		if solver.candidate_count() == 0 {
+			if is_tty {
+				println!("Your feedback is inconsistent, no word in the answer list matches all of it:");
+				for (word, colors) in &history {
+					let tmp = utils::split_letters(word).into_iter().zip(colors.chars());
+					for (ch, col) in tmp {
+						utils::print_with_color(&ch, col);
+					}
+					println!();
+				}
+			}
+			else{
+				println!("INCONSISTENT");
+			}
+			return Err(format!("feedback {} for {} contradicts the earlier feedback ({} candidates before it)",
+				pattern, guess, before).into());
+		}
+	}
+}
+
+/// A pattern is well-formed if it has one of G, Y, R per letter, and possible if no letter
+/// is marked R before a Y of the same letter: yellows are handed out from the left.
+fn check_pattern(guess: &str, pattern: &str, word_dict: &WordDict) -> Result<(), String> {
+	let letters = word_dict.alphabet.indices(guess).unwrap_or_default();
+	if pattern.chars().count() != letters.len() || !pattern.chars().all(|col| "GYR".contains(col)) {
+		return Err(format!("Expected {} letters of G, Y or R", letters.len()));
+	}
+	let mut absent = vec![false; word_dict.alphabet.len()];
+	for (letter, col) in letters.iter().zip(pattern.chars()) {
+		match col {
+			'R' => absent[*letter] = true,
+			'Y' if absent[*letter] => {
+				return Err(format!("{} is marked R before it is marked Y", word_dict.alphabet.letters()[*letter]));
+			},
+			_ => (),
+		}
+	}
+	Ok(())
+}
@@ -0,0 +1,38 @@
+// The shareable result block: a title line followed by one row of squares per guess
+
+use crate::interact_model::{GameInfo, GUESS_CHANCE};
+
+/// `puzzle` identifies the puzzle in the title, e.g. the daily puzzle number.
+pub fn share_text(gameinfo: &GameInfo, puzzle: Option<&str>, high_contrast: bool) -> String {
+	let history = gameinfo.guess_history();
+	let score = match gameinfo.result() {
+		Some(true) => history.len().to_string(),
+		_ => String::from("X"),
+	};
+	let mut text = String::from("Wordle");
+	if let Some(puzzle) = puzzle {
+		text.push_str(&format!(" {}", puzzle));
+	}
+	text.push_str(&format!(" {}/{}", score, GUESS_CHANCE));
+	if gameinfo.is_difficult() {
+		text.push('*');
+	}
+	text.push_str("\n\n");
+	for (_, guess_status) in history {
+		for col in guess_status.chars() {
+			text.push_str(square(col, high_contrast));
+		}
+		text.push('\n');
+	}
+	text
+}
+
+fn square(col: char, high_contrast: bool) -> &'static str {
+	match (col, high_contrast) {
+		('G', false) => "🟩",
+		('Y', false) => "🟨",
+		('G', true) => "🟧",
+		('Y', true) => "🟦",
+		_ => "⬛",
+	}
+}
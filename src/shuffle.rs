@@ -0,0 +1,130 @@
+// Seed-to-answer mapping
+//
+// The order answers are drawn in must never change for a given seed, otherwise
+// `--seed S --day N` shared between players would point at different words after
+// an update. The algorithm is therefore implemented here instead of relying on the
+// `rand` crate, whose output is not guaranteed to be stable across releases.
+//
+// Version 1 (the only version so far) is bit-for-bit the order produced by
+// `rand` 0.8's `StdRng::seed_from_u64(seed)` followed by `SliceRandom::shuffle`,
+// which is what every release before the versioned algorithm used:
+//
+//   1. The 64-bit seed is expanded into a 32-byte key with PCG32
+//      (multiplier 6364136223846793005, increment 11634580027462260723):
+//      for each of the 8 key words, `state = state * MUL + INC`, then the
+//      word is `rotr32(((state >> 18) ^ state) >> 27, state >> 59)`.
+//   2. A ChaCha stream cipher with 12 rounds, that key, a zero nonce and a
+//      64-bit block counter starting at 0 produces a stream of 32-bit words.
+//   3. Fisher-Yates from the back: for i = len-1 down to 1, draw j uniformly
+//      from 0..=i and swap elements i and j. A draw takes words `v` from the
+//      stream until the low 32 bits of `v * (i + 1)` are at most
+//      `((i + 1) << leading_zeros(i + 1)) - 1`; j is the high 32 bits.
+//
+// A new algorithm must get a new version number, never replace an old one.
+
+pub const DEFAULT_SEED: u64 = 114514;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ShuffleVersion {
+	V1,
+}
+
+impl ShuffleVersion {
+	pub fn from_number(version: u32) -> Result<ShuffleVersion, Box<dyn std::error::Error>> {
+		match version {
+			1 => Ok(ShuffleVersion::V1),
+			_ => Err(format!("unknown shuffle version {}, only version 1 is supported", version).into()),
+		}
+	}
+}
+
+/// The order answers are drawn in: a permutation of `0..len` determined by `seed`.
+pub fn answer_order(len: usize, seed: u64, version: ShuffleVersion) -> Vec<usize> {
+	let mut order: Vec<usize> = (0..len).collect();
+	match version {
+		ShuffleVersion::V1 => {
+			let mut stream = ChaCha12::from_seed(seed);
+			for i in (1..order.len()).rev() {
+				let j = stream.below(i as u32 + 1) as usize;
+				order.swap(i, j);
+			}
+		},
+	}
+	order
+}
+
+struct ChaCha12 {
+	key: [u32; 8],
+	counter: u64,
+	buffer: [u32; 64],
+	index: usize,
+}
+
+impl ChaCha12 {
+	fn from_seed(mut state: u64) -> ChaCha12 {
+		const MUL: u64 = 6364136223846793005;
+		const INC: u64 = 11634580027462260723;
+		let mut key = [0_u32; 8];
+		for word in key.iter_mut() {
+			state = state.wrapping_mul(MUL).wrapping_add(INC);
+			let xorshifted = (((state >> 18) ^ state) >> 27) as u32;
+			*word = xorshifted.rotate_right((state >> 59) as u32);
+		}
+		ChaCha12 { key, counter: 0, buffer: [0; 64], index: 64 }
+	}
+
+	fn next_u32(&mut self) -> u32 {
+		if self.index >= self.buffer.len() {
+			// four blocks at a time, like the reference implementation
+			for block in 0..4 {
+				let output = self.block(self.counter);
+				self.buffer[block * 16..(block + 1) * 16].copy_from_slice(&output);
+				self.counter = self.counter.wrapping_add(1);
+			}
+			self.index = 0;
+		}
+		self.index += 1;
+		self.buffer[self.index - 1]
+	}
+
+	/// Uniform in `0..range` by widening multiplication with rejection, `range` > 0.
+	fn below(&mut self, range: u32) -> u32 {
+		let zone = (range << range.leading_zeros()).wrapping_sub(1);
+		loop {
+			let product = (self.next_u32() as u64) * (range as u64);
+			if (product as u32) <= zone {
+				return (product >> 32) as u32;
+			}
+		}
+	}
+
+	fn block(&self, counter: u64) -> [u32; 16] {
+		let mut input = [0_u32; 16];
+		input[..4].copy_from_slice(&[0x61707865, 0x3320646e, 0x79622d32, 0x6b206574]);
+		input[4..12].copy_from_slice(&self.key);
+		input[12] = counter as u32;
+		input[13] = (counter >> 32) as u32;
+		let mut x = input;
+		for _ in 0..6 {
+			quarter_round(&mut x, 0, 4, 8, 12);
+			quarter_round(&mut x, 1, 5, 9, 13);
+			quarter_round(&mut x, 2, 6, 10, 14);
+			quarter_round(&mut x, 3, 7, 11, 15);
+			quarter_round(&mut x, 0, 5, 10, 15);
+			quarter_round(&mut x, 1, 6, 11, 12);
+			quarter_round(&mut x, 2, 7, 8, 13);
+			quarter_round(&mut x, 3, 4, 9, 14);
+		}
+		for (word, initial) in x.iter_mut().zip(input) {
+			*word = word.wrapping_add(initial);
+		}
+		x
+	}
+}
+
+fn quarter_round(x: &mut [u32; 16], a: usize, b: usize, c: usize, d: usize) {
+	x[a] = x[a].wrapping_add(x[b]); x[d] = (x[d] ^ x[a]).rotate_left(16);
+	x[c] = x[c].wrapping_add(x[d]); x[b] = (x[b] ^ x[c]).rotate_left(12);
+	x[a] = x[a].wrapping_add(x[b]); x[d] = (x[d] ^ x[a]).rotate_left(8);
+	x[c] = x[c].wrapping_add(x[d]); x[b] = (x[b] ^ x[c]).rotate_left(7);
+}
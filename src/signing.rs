@@ -0,0 +1,181 @@
+// Tamper-evident histories: --sign-key
+//
+// Every game saved with a key carries an HMAC-SHA256 over the signature of the game before
+// it and the game itself, so editing, removing or reordering signed games breaks the chain
+// from there on. Games saved without the key stay unsigned, are skipped by the chain and
+// are left out of the statistics checked with the key, so they cannot pass for signed ones.
+// Only the games are signed, not the match score or the achievements, and games cut from
+// the end of the history cannot be noticed.
+
+use std::fs;
+use hmac::{Hmac, Mac};
+use serde_json::{json, Value};
+use sha2::Sha256;
+use crate::history_store::HistoryStore;
+use crate::json_parser::{Gamejson, Games};
+
+type HmacSha256 = Hmac<Sha256>;
+
+pub fn read_key(address: &str) -> Result<Vec<u8>, Box<dyn std::error::Error>> {
+	let key = fs::read(address).map_err(|err| format!("cannot read the signing key {}: {}", address, err))?;
+	if key.is_empty() {
+		return Err(format!("the signing key {} is empty", address).into());
+	}
+	Ok(key)
+}
+
+fn signature(key: &[u8], previous: &str, game: &Games) -> String {
+	let mut game = game.clone();
+	game.set_signature(None);
+	let mut mac = HmacSha256::new_from_slice(key).expect("HMAC takes keys of any length");
+	mac.update(previous.as_bytes());
+	mac.update(b"\n");
+	mac.update(serde_json::to_string(&game).unwrap().as_bytes());
+	mac.finalize().into_bytes().iter().map(|byte| format!("{:02x}", byte)).collect()
+}
+
+/// Signs the games added after loading before they are saved
+pub struct SignedStore {
+	inner: Box<dyn HistoryStore>,
+	key: Vec<u8>,
+	loaded: usize,
+}
+
+impl SignedStore {
+	pub fn new(inner: Box<dyn HistoryStore>, key: Vec<u8>) -> SignedStore {
+		SignedStore { inner, key, loaded: 0 }
+	}
+
+	fn signed(&self, gamejson: &Gamejson) -> Gamejson {
+		let mut gamejson = gamejson.clone();
+		let mut previous = gamejson.games()[..self.loaded].iter().rev()
+			.find_map(|game| game.signature().map(String::from))
+			.unwrap_or_default();
+		for game in gamejson.games_mut()[self.loaded..].iter_mut() {
+			let signature = signature(&self.key, &previous, game);
+			game.set_signature(Some(signature.clone()));
+			previous = signature;
+		}
+		gamejson
+	}
+}
+
+impl HistoryStore for SignedStore {
+	fn load(&mut self) -> Result<Gamejson, Box<dyn std::error::Error>> {
+		let gamejson = self.inner.load()?;
+		self.loaded = gamejson.games().len();
+		Ok(gamejson)
+	}
+
+	fn save(&mut self, gamejson: &Gamejson) -> Result<(), Box<dyn std::error::Error>> {
+		self.inner.save(&self.signed(gamejson))
+	}
+
+	fn rewrite(&mut self, gamejson: &Gamejson) -> Result<(), Box<dyn std::error::Error>> {
+		self.inner.rewrite(&self.signed(gamejson))
+	}
+}
+
+pub struct Verification {
+	signed: usize,
+	unsigned: usize,
+	/// The first game whose signature does not match, from 1
+	broken: Option<usize>,
+	/// The signed games before the broken one
+	trusted: Gamejson,
+}
+
+impl Verification {
+	pub fn broken(&self) -> Option<usize> {
+		self.broken
+	}
+
+	fn to_json(&self) -> Value {
+		json!({ "verified": self.broken.is_none(), "signed": self.signed, "unsigned": self.unsigned, "broken": self.broken })
+	}
+}
+
+/// Follow the chain of the signed games, counting the unsigned ones wherever they are
+pub fn verify(key: &[u8], gamejson: &Gamejson) -> Verification {
+	let mut verification = Verification { signed: 0, unsigned: 0, broken: None, trusted: Gamejson::new() };
+	let mut previous = String::new();
+	for (id, game) in gamejson.games().iter().enumerate() {
+		match game.signature() {
+			None => verification.unsigned += 1,
+			Some(found) if found == signature(key, &previous, game) => {
+				verification.signed += 1;
+				verification.trusted.add_games(game.clone());
+				previous = String::from(found);
+			},
+			Some(_) => {
+				verification.broken = Some(id + 1);
+				break;
+			},
+		}
+	}
+	verification
+}
+
+/// The games the statistics count: every game without a key, with one only the signed games
+/// that verify, then the games played since loading, which the store signs as they are saved
+pub struct Counted {
+	gamejson: Gamejson,
+	/// How the history verified when it was loaded
+	verification: Option<Verification>,
+}
+
+impl Counted {
+	pub fn new(sign_key: Option<&str>, gamejson: &Gamejson) -> Result<Counted, Box<dyn std::error::Error>> {
+		match sign_key {
+			Some(address) => {
+				let verification = verify(&read_key(address)?, gamejson);
+				Ok(Counted { gamejson: verification.trusted.clone(), verification: Some(verification) })
+			},
+			None => Ok(Counted { gamejson: gamejson.clone(), verification: None }),
+		}
+	}
+
+	pub fn add_game(&mut self, game: Games) {
+		self.gamejson.add_games(game);
+	}
+
+	pub fn gamejson(&self) -> &Gamejson {
+		&self.gamejson
+	}
+
+	pub fn verification(&self) -> Option<&Verification> {
+		self.verification.as_ref()
+	}
+
+	/// The statistics for the JSON interfaces, with the verification when there is a key
+	pub fn to_json(&self) -> Value {
+		let mut stats = self.gamejson.to_stats().to_json();
+		if let Some(verification) = &self.verification {
+			stats["verification"] = verification.to_json();
+		}
+		stats
+	}
+}
+
+/// Sign the signed games again in their order, after games were changed or added with the key
+pub fn resign(key: &[u8], gamejson: &Gamejson) -> Gamejson {
+	let mut gamejson = gamejson.clone();
+	let mut previous = String::new();
+	for game in gamejson.games_mut().iter_mut().filter(|game| game.signature().is_some()) {
+		let signature = signature(key, &previous, game);
+		game.set_signature(Some(signature.clone()));
+		previous = signature;
+	}
+	gamejson
+}
+
+pub fn print_verification(verification: &Verification, is_tty: bool) {
+	match (verification.broken, is_tty) {
+		(None, true) => println!("The history verifies: {} signed games counted, {} unsigned left out",
+			verification.signed, verification.unsigned),
+		(Some(game), true) => println!("The history does not verify: game #{} was changed, removed or added without the key, only the {} signed games before it are counted",
+			game, verification.signed),
+		(None, false) => println!("VERIFIED {} {}", verification.signed, verification.unsigned),
+		(Some(game), false) => println!("TAMPERED {}", game),
+	}
+}
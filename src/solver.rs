@@ -0,0 +1,94 @@
+// A simple solver: keeps the answers consistent with the feedback so far and
+// picks the guess that splits them into the most informative groups
+
+use std::collections::BTreeMap;
+use crate::interact_model::score_guess;
+use crate::utils::WordDict;
+
+/// Guesses considered when looking for the best one, taken by letter frequency
+const GUESS_POOL: usize = 100;
+
+pub struct Solver<'a> {
+	word_dict: &'a WordDict,
+	/// Remaining possible answers as indices into `final_list`
+	candidates: Vec<usize>,
+	letters: Vec<Vec<usize>>,
+}
+
+impl<'a> Solver<'a> {
+	pub fn new(word_dict: &'a WordDict) -> Solver<'a> {
+		let letters: Vec<Vec<usize>> = word_dict.final_list.iter()
+			.map(|word| word_dict.alphabet.indices(word).unwrap_or_default())
+			.collect();
+		Solver { word_dict, candidates: (0..letters.len()).collect(), letters }
+	}
+
+	pub fn candidate_count(&self) -> usize {
+		self.candidates.len()
+	}
+
+	/// Keep only the answers that would have produced `pattern` for `guess`.
+	pub fn apply(&mut self, guess: &str, pattern: &str) {
+		let guess = self.word_dict.alphabet.indices(guess).unwrap_or_default();
+		let size = self.word_dict.alphabet.len();
+		let letters = &self.letters;
+		self.candidates.retain(|id| score_guess(&letters[*id], &guess, size) == pattern);
+	}
+
+	/// Expected information in bits of playing `guess` against the remaining candidates.
+	pub fn entropy(&self, guess: &str) -> f64 {
+		self.entropy_of(&self.word_dict.alphabet.indices(guess).unwrap_or_default())
+	}
+
+	fn entropy_of(&self, guess: &[usize]) -> f64 {
+		let size = self.word_dict.alphabet.len();
+		let mut groups: BTreeMap<String, usize> = BTreeMap::new();
+		for id in &self.candidates {
+			*groups.entry(score_guess(&self.letters[*id], guess, size)).or_insert(0) += 1;
+		}
+		let total = self.candidates.len() as f64;
+		groups.values().map(|count| {
+			let p = *count as f64 / total;
+			p * (1.0 / p).log2()
+		}).sum()
+	}
+
+	/// The candidate with the highest entropy, `None` if no candidate is left.
+	/// Only the most letter-frequent candidates are scored to keep this fast on big lists.
+	pub fn best_guess(&self) -> Option<String> {
+		if self.candidates.len() <= 2 {
+			return self.candidates.first().map(|id| self.word_dict.final_list[*id].clone());
+		}
+		let size = self.word_dict.alphabet.len();
+		let mut frequency = vec![0; size];
+		for id in &self.candidates {
+			let mut seen = vec![false; size];
+			for letter in &self.letters[*id] {
+				if !seen[*letter] {
+					seen[*letter] = true;
+					frequency[*letter] += 1;
+				}
+			}
+		}
+		let mut pool = self.candidates.clone();
+		let coverage = |id: &usize| -> usize {
+			let mut letters = self.letters[*id].clone();
+			letters.sort();
+			letters.dedup();
+			letters.iter().map(|letter| frequency[*letter]).sum()
+		};
+		pool.sort_by_key(|id| std::cmp::Reverse(coverage(id)));
+		pool.truncate(GUESS_POOL);
+
+		let mut best: Option<(f64, usize)> = None;
+		for id in pool {
+			let entropy = self.entropy_of(&self.letters[id]);
+			match best {
+				// equal splits can differ in the last bits, keep the earlier word then
+				Some((value, _)) if entropy <= value + 1e-9 => (),
+				_ => best = Some((entropy, id)),
+			}
+		}
+		best.map(|(_, id)| self.word_dict.final_list[id].clone())
+	}
+}
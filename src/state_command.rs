@@ -0,0 +1,296 @@
+// Maintenance of the game history: `wordle state ...`
+
+use std::collections::BTreeMap;
+use clap::Subcommand;
+use crate::history_store;
+use crate::interact_model::{GameInfo, GUESS_CHANCE};
+use crate::json_parser::Gamejson;
+use crate::signing;
+use crate::utils::WordDict;
+
+#[derive(Subcommand, Debug, Clone)]
+pub enum StateAction {
+	/// Combine histories into one, dropping games that appear more than once. The output
+	/// keeps its own games; --state, if given, is read as well and is the default output.
+	/// Signed histories are only merged with --sign-key, which chains the signed games again.
+	Merge {
+		/// Histories to read, as for --state
+		inputs: Vec<String>,
+		#[arg(short, long)]
+		output: Option<String>,
+	},
+	/// Check every game of --state against the word list and the rules of the game
+	Check,
+	/// Check --state and fix what can be fixed: empty games, extra guesses and the round count.
+	/// A signed history is only fixed with --sign-key, which signs it again.
+	Repair,
+}
+
+pub fn run(action: StateAction, state: Option<&str>, sign_key: Option<&str>, word_dict: &WordDict, pack: &str, is_tty: bool) -> Result<(), Box<dyn std::error::Error>> {
+	match action {
+		StateAction::Merge { inputs, output } => {
+			let output = output.as_deref().or(state).ok_or("merge needs somewhere to write, pass -o or --state")?;
+			let mut addresses = vec![output];
+			addresses.extend(state);
+			addresses.extend(inputs.iter().map(String::as_str));
+			let mut unique: Vec<&str> = Vec::new();
+			for address in addresses {
+				if !unique.contains(&address) {
+					unique.push(address);
+				}
+			}
+
+			let mut store = history_store::open(output)?;
+			let mut histories = vec![(output, store.load()?)];
+			for address in &unique[1..] {
+				histories.push((*address, history_store::open(address)?.load()?));
+			}
+			let key = signing_key(sign_key, &histories)?;
+			let mut merge = merge(&histories);
+			if let Some(key) = &key {
+				merge.gamejson = signing::resign(key, &merge.gamejson);
+			}
+			store.save(&merge.gamejson)?;
+			print_merge(&merge, output, is_tty);
+			Ok(())
+		},
+		StateAction::Check | StateAction::Repair => {
+			let repair = matches!(action, StateAction::Repair);
+			let state = state.ok_or("check needs the state file, pass it with --state")?;
+			let mut store = history_store::open(state)?;
+			let gamejson = store.load()?;
+			let check = check(&gamejson, word_dict, pack);
+			if repair && check.problems.iter().any(|problem| problem.fix.is_some()) {
+				let mut repaired = apply_fixes(&gamejson, &check.problems);
+				if let Some(key) = signing_key(sign_key, &[(state, gamejson)])? {
+					repaired = signing::resign(&key, &repaired);
+				}
+				store.rewrite(&repaired)?;
+			}
+			print_check(&check, repair, is_tty);
+			Ok(())
+		},
+	}
+}
+
+/// The key to sign the changed histories again. Signed histories are only changed with the
+/// key and only while they verify, so signing them again cannot vouch for edits made without it.
+fn signing_key(sign_key: Option<&str>, histories: &[(&str, Gamejson)]) -> Result<Option<Vec<u8>>, Box<dyn std::error::Error>> {
+	let address = match sign_key {
+		Some(address) => address,
+		None => return match histories.iter().find(|(_, history)| history.games().iter().any(|game| game.signature().is_some())) {
+			Some((address, _)) => Err(format!("{} holds signed games, pass --sign-key to sign the changes", address).into()),
+			None => Ok(None),
+		},
+	};
+	let key = signing::read_key(address)?;
+	for (address, history) in histories {
+		if let Some(game) = signing::verify(&key, history).broken() {
+			return Err(format!("{} does not verify from game #{} on, it was changed without the key", address, game).into());
+		}
+	}
+	Ok(Some(key))
+}
+
+struct Merge {
+	gamejson: Gamejson,
+	read: usize,
+	duplicates: usize,
+	conflicts: Vec<String>,
+}
+
+/// The games in the order they are read, the first copy of the same game only
+fn merge(histories: &[(&str, Gamejson)]) -> Merge {
+	let mut gamejson = Gamejson::new();
+	let mut read = 0;
+	let mut duplicates = 0;
+	let mut conflicts = Vec::new();
+	let mut match_score: BTreeMap<String, (i32, &str)> = BTreeMap::new();
+
+	for (number, (address, history)) in histories.iter().enumerate() {
+		if history.get_total() as usize != history.games().len() {
+			conflicts.push(format!("{} says {} rounds but holds {} games, counted the games",
+				address, history.get_total(), history.games().len()));
+		}
+		for game in history.games() {
+			read += 1;
+			// the output keeps its own games, even repeated ones
+			if number > 0 && gamejson.games().iter().any(|kept| kept.same_game(game)) {
+				duplicates += 1;
+				continue;
+			}
+			if let (Some(puzzle), Some(date)) = (game.puzzle(), game.date()) {
+				if gamejson.has_daily(game.pack(), date) {
+					conflicts.push(format!("daily puzzle #{} was played differently in {}, kept both", puzzle, address));
+				}
+			}
+			gamejson.add_games(game.clone());
+		}
+		for (player, points) in history.match_score() {
+			match match_score.get(player).copied() {
+				Some((kept, _)) if kept == *points => (),
+				Some((kept, from)) => {
+					conflicts.push(format!("{} has {} points in {} and {} in {}, kept {}",
+						player, kept, from, points, address, kept.max(*points)));
+					if *points > kept {
+						match_score.insert(player.clone(), (*points, address));
+					}
+				},
+				None => {
+					match_score.insert(player.clone(), (*points, address));
+				},
+			}
+		}
+		for id in history.achievements() {
+			if !gamejson.achievements().contains(id) {
+				gamejson.add_achievement(id);
+			}
+		}
+	}
+	gamejson.set_match_score(match_score.into_iter().map(|(player, (points, _))| (player, points)).collect());
+	Merge { gamejson, read, duplicates, conflicts }
+}
+
+fn print_merge(merge: &Merge, output: &str, is_tty: bool) {
+	let games = merge.gamejson.games();
+	if is_tty {
+		println!("Read {} games, skipped {} duplicates, wrote {} games to {}", merge.read, merge.duplicates, games.len(), output);
+		for conflict in &merge.conflicts {
+			println!("Conflict: {}", conflict);
+		}
+	}
+	else{
+		println!("MERGED {} {} {}", merge.read, merge.duplicates, games.len());
+		for conflict in &merge.conflicts {
+			println!("CONFLICT {}", conflict);
+		}
+	}
+}
+
+enum Fix {
+	Drop,
+	/// Keep only this many guesses
+	Truncate(usize),
+	/// Count the games again for total_rounds
+	Recount,
+}
+
+struct Problem {
+	/// The game, from 1, none for the whole file
+	game: Option<usize>,
+	text: String,
+	fix: Option<Fix>,
+}
+
+struct Check {
+	games: usize,
+	/// Games of another word pack, which cannot be checked with this word list
+	skipped: usize,
+	problems: Vec<Problem>,
+}
+
+fn check(gamejson: &Gamejson, word_dict: &WordDict, pack: &str) -> Check {
+	let mut problems = Vec::new();
+	let mut skipped = 0;
+	if gamejson.get_total() as usize != gamejson.games().len() {
+		problems.push(Problem { game: None, fix: Some(Fix::Recount),
+			text: format!("total_rounds is {} but there are {} games", gamejson.get_total(), gamejson.games().len()) });
+	}
+	for (id, game) in gamejson.games().iter().enumerate() {
+		let number = Some(id + 1);
+		let guesses = game.get_guesses();
+		if guesses.is_empty() {
+			problems.push(Problem { game: number, text: String::from("has no guesses"), fix: Some(Fix::Drop) });
+			continue;
+		}
+		if game.pack() != pack {
+			skipped += 1;
+			continue;
+		}
+		if !is_word(game.answer(), word_dict) {
+			problems.push(Problem { game: number, text: format!("the answer {} is not in the word list", game.answer()), fix: None });
+			continue;
+		}
+		let mut gameinfo = GameInfo::new(game.answer(), game.difficult(), word_dict);
+		for (step, guess) in guesses.iter().enumerate() {
+			if !gameinfo.game_is_running() {
+				let text = match gameinfo.result() {
+					Some(true) => String::from("goes on after the answer was found"),
+					_ => format!("has more than {} guesses", GUESS_CHANCE),
+				};
+				problems.push(Problem { game: number, text, fix: Some(Fix::Truncate(step)) });
+				break;
+			}
+			if gameinfo.make_guess(guess, word_dict).is_err() {
+				let text = match is_word(guess, word_dict) {
+					true => format!("guess {} {} breaks the difficult mode rules", step + 1, guess),
+					false => format!("guess {} {} is not in the word list", step + 1, guess),
+				};
+				problems.push(Problem { game: number, text, fix: None });
+				break;
+			}
+		}
+		if gameinfo.game_is_running() && gameinfo.guess_history().len() == guesses.len() {
+			problems.push(Problem { game: number, fix: None,
+				text: format!("is lost after only {} of {} guesses", guesses.len(), GUESS_CHANCE) });
+		}
+	}
+	Check { games: gamejson.games().len(), skipped, problems }
+}
+
+fn is_word(word: &str, word_dict: &WordDict) -> bool {
+	word_dict.alphabet.indices(word).is_some_and(|letters| letters.len() == word_dict.length) && word_dict.vaild(word)
+}
+
+/// The history with the games dropped or cut short, counted again
+fn apply_fixes(gamejson: &Gamejson, problems: &[Problem]) -> Gamejson {
+	let mut repaired = Gamejson::new();
+	for (id, game) in gamejson.games().iter().enumerate() {
+		let mut game = game.clone();
+		match problems.iter().find(|problem| problem.game == Some(id + 1)).and_then(|problem| problem.fix.as_ref()) {
+			Some(Fix::Drop) => continue,
+			Some(Fix::Truncate(len)) => game.truncate_guesses(*len),
+			_ => (),
+		}
+		repaired.add_games(game);
+	}
+	repaired.set_match_score(gamejson.match_score().clone());
+	for id in gamejson.achievements() {
+		repaired.add_achievement(id);
+	}
+	repaired
+}
+
+fn print_check(check: &Check, repair: bool, is_tty: bool) {
+	for problem in &check.problems {
+		let place = match problem.game {
+			Some(number) => format!("#{}", number),
+			None => String::from("file"),
+		};
+		let status = match (&problem.fix, repair) {
+			(None, _) => "INVALID",
+			(Some(_), false) => "FIXABLE",
+			(Some(_), true) => "FIXED",
+		};
+		if is_tty {
+			let note = match status {
+				"FIXABLE" => " (state repair can fix this)",
+				"FIXED" => " (fixed)",
+				_ => "",
+			};
+			match problem.game {
+				Some(number) => println!("Game #{} {}{}", number, problem.text, note),
+				None => println!("The state file: {}{}", problem.text, note),
+			}
+		}
+		else{
+			println!("{} {} {}", status, place, problem.text);
+		}
+	}
+	if is_tty {
+		println!("Checked {} games, {} of another word pack skipped, {} problems", check.games, check.skipped, check.problems.len());
+	}
+	else{
+		println!("CHECKED {} {} {}", check.games, check.skipped, check.problems.len());
+	}
+}
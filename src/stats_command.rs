@@ -0,0 +1,270 @@
+// Reports on the games of the state file: `wordle stats ...`
+
+use chrono::Duration;
+use clap::{Args, Subcommand, ValueEnum};
+use serde_json::json;
+use std::cmp::Reverse;
+use std::collections::BTreeMap;
+use std::fs;
+use crate::achievements;
+use crate::daily;
+use crate::json_parser::{Gamejson, Games};
+use crate::letter_stats;
+use crate::utils::{WordDict, DEFAULT_TOP};
+
+#[derive(Subcommand, Debug, Clone)]
+pub enum StatsAction {
+	/// Print the statistics like --stats does, for the selected games only
+	Show {
+		#[command(flatten)]
+		filter: StatsFilter,
+		/// Number of favorite words listed
+		#[arg(long, default_value_t = DEFAULT_TOP)]
+		top: usize,
+	},
+	/// Rank the opening words, with the win rate and average guesses of each
+	Openers {
+		#[command(flatten)]
+		filter: StatsFilter,
+		/// Number of openers listed
+		#[arg(long, default_value_t = DEFAULT_TOP)]
+		top: usize,
+	},
+	/// Which letters and positions cause the most trouble
+	Letters {
+		#[command(flatten)]
+		filter: StatsFilter,
+	},
+	/// Every achievement and whether the selected games earned it
+	Achievements {
+		#[command(flatten)]
+		filter: StatsFilter,
+	},
+	/// Write one row per game and a summary, for spreadsheets and scripts
+	Export {
+		#[command(flatten)]
+		filter: StatsFilter,
+		#[arg(long, value_enum, default_value_t = ExportFormat::Csv)]
+		format: ExportFormat,
+		#[arg(short, long)]
+		output: Option<String>,
+	},
+}
+
+/// Which games to count, every game by default. The date filters go by the day a game was
+/// played; older histories only know the day of daily puzzles, their other games are left out.
+#[derive(Args, Debug, Clone)]
+pub struct StatsFilter {
+	/// Only games played on or after this day, YYYY-MM-DD
+	#[arg(long)]
+	since: Option<String>,
+	/// Only games played on or before this day, YYYY-MM-DD
+	#[arg(long)]
+	until: Option<String>,
+	/// Only games of the last N days, today included
+	#[arg(long, conflicts_with = "since", value_parser = clap::value_parser!(u32).range(1..))]
+	last_days: Option<u32>,
+	#[arg(long, value_enum)]
+	difficulty: Option<Difficulty>,
+	/// Only games of this word pack
+	#[arg(long)]
+	pack: Option<String>,
+	/// Only games this player guessed in two-player mode
+	#[arg(long)]
+	player: Option<String>,
+	#[arg(long, value_enum)]
+	result: Option<GameResult>,
+}
+
+#[derive(ValueEnum, Debug, Clone, Copy, PartialEq)]
+pub enum Difficulty {
+	Normal,
+	Hard,
+}
+
+#[derive(ValueEnum, Debug, Clone, Copy, PartialEq)]
+pub enum GameResult {
+	Won,
+	Lost,
+}
+
+#[derive(ValueEnum, Debug, Clone, Copy)]
+pub enum ExportFormat {
+	Csv,
+	Json,
+}
+
+const CSV_COLUMNS: &str = "game,answer,result,attempts,guesses,mode,difficult,pack,date,puzzle,player,played";
+
+pub fn run(action: StatsAction, gamejson: &Gamejson, word_dict: &WordDict, pack: &str, is_tty: bool) -> Result<(), Box<dyn std::error::Error>> {
+	match action {
+		StatsAction::Show { filter, top } => {
+			let mut stats = filter.apply(gamejson)?.to_stats();
+			stats.set_top(top);
+			stats.print_result(is_tty);
+			Ok(())
+		},
+		StatsAction::Openers { filter, top } => {
+			let mut openers = openers(&filter.apply(gamejson)?);
+			openers.truncate(top);
+			print_openers(&openers, is_tty);
+			Ok(())
+		},
+		StatsAction::Letters { filter } => {
+			let gamejson = filter.apply(gamejson)?.filtered(|game| game.pack() == pack);
+			letter_stats::run(&gamejson, word_dict, is_tty);
+			Ok(())
+		},
+		StatsAction::Achievements { filter } => {
+			achievements::print_achievements(&filter.apply(gamejson)?, is_tty);
+			Ok(())
+		},
+		StatsAction::Export { filter, format, output } => {
+			let gamejson = &filter.apply(gamejson)?;
+			let content = match format {
+				ExportFormat::Csv => export_csv(gamejson),
+				ExportFormat::Json => format!("{}\n", serde_json::to_string_pretty(&export_json(gamejson))?),
+			};
+			match output {
+				Some(address) => fs::write(address, content)?,
+				None => print!("{}", content),
+			}
+			Ok(())
+		},
+	}
+}
+
+impl StatsFilter {
+	fn apply(&self, gamejson: &Gamejson) -> Result<Gamejson, Box<dyn std::error::Error>> {
+		let since = match (&self.since, self.last_days) {
+			(Some(since), _) => Some(daily::parse_date(since)?),
+			(None, Some(days)) => Some(daily::today() - Duration::days(days as i64 - 1)),
+			(None, None) => None,
+		};
+		let until = match &self.until {
+			Some(until) => Some(daily::parse_date(until)?),
+			None => None,
+		};
+		Ok(gamejson.filtered(|game| {
+			if since.is_some() || until.is_some() {
+				let date = match game.played_on() {
+					Some(date) => date,
+					None => return false,
+				};
+				if since.is_some_and(|since| date < since) || until.is_some_and(|until| date > until) {
+					return false;
+				}
+			}
+			let difficulty = if game.difficult() { Difficulty::Hard } else { Difficulty::Normal };
+			let result = if game.check_result() { GameResult::Won } else { GameResult::Lost };
+			self.difficulty.is_none_or(|wanted| wanted == difficulty)
+				&& self.pack.as_ref().is_none_or(|pack| pack == game.pack())
+				&& self.player.as_ref().is_none_or(|player| Some(player.as_str()) == game.player())
+				&& self.result.is_none_or(|wanted| wanted == result)
+		}))
+	}
+}
+
+struct Opener {
+	word: String,
+	games: usize,
+	wins: usize,
+	/// Guesses used in the won games
+	attempts: usize,
+}
+
+/// Every opening word, the most played first and ties in alphabetical order
+fn openers(gamejson: &Gamejson) -> Vec<Opener> {
+	let mut openers: BTreeMap<String, Opener> = BTreeMap::new();
+	for game in gamejson.games() {
+		let word = match game.get_guesses().into_iter().next() {
+			Some(word) => word,
+			None => continue,
+		};
+		let opener = openers.entry(word.clone()).or_insert(Opener { word, games: 0, wins: 0, attempts: 0 });
+		opener.games += 1;
+		if game.check_result() {
+			opener.wins += 1;
+			opener.attempts += game.guesses_number() as usize;
+		}
+	}
+	let mut openers: Vec<Opener> = openers.into_values().collect();
+	openers.sort_by_key(|opener| Reverse(opener.games));
+	openers
+}
+
+fn print_openers(openers: &[Opener], is_tty: bool) {
+	for (rank, opener) in openers.iter().enumerate() {
+		let win_rate = opener.wins as f64 * 100.0 / opener.games as f64;
+		let average = match opener.wins {
+			0 => 0.0,
+			_ => opener.attempts as f64 / opener.wins as f64,
+		};
+		if is_tty {
+			println!("Rank {}: {}, opened {} games, won {:.2}%, {:.2} guesses on average in won games",
+				rank + 1, opener.word, opener.games, win_rate, average);
+		}
+		else{
+			println!("{} {} {} {:.2} {:.2}", opener.word, opener.games, opener.wins, win_rate, average);
+		}
+	}
+}
+
+fn result(game: &Games) -> &'static str {
+	match game.check_result() {
+		true => "won",
+		false => "lost",
+	}
+}
+
+/// The games, then a blank line and the summary as a second table
+fn export_csv(gamejson: &Gamejson) -> String {
+	let mut content = format!("{}\n", CSV_COLUMNS);
+	for (id, game) in gamejson.games().iter().enumerate() {
+		let row = [(id + 1).to_string(), String::from(game.answer()), String::from(result(game)),
+			game.guesses_number().to_string(), game.get_guesses().join(" "), String::from(game.mode()),
+			game.difficult().to_string(), String::from(game.pack()),
+			String::from(game.date().unwrap_or_default()), game.puzzle().map(|puzzle| puzzle.to_string()).unwrap_or_default(),
+			String::from(game.player().unwrap_or_default()), String::from(game.played().unwrap_or_default())];
+		let row: Vec<String> = row.iter().map(|field| csv_field(field)).collect();
+		content.push_str(&row.join(","));
+		content.push('\n');
+	}
+	let summary = gamejson.to_stats().to_json();
+	content.push_str("\ngames,wins,losses,average_attempts\n");
+	content.push_str(&format!("{},{},{},{:.2}\n", gamejson.games().len(), summary["wins"], summary["losses"],
+		summary["average_attempts"].as_f64().unwrap_or(0.0)));
+	content
+}
+
+fn export_json(gamejson: &Gamejson) -> serde_json::Value {
+	let games: Vec<serde_json::Value> = gamejson.games().iter().enumerate().map(|(id, game)| {
+		json!({
+			"game": id + 1,
+			"answer": game.answer(),
+			"result": result(game),
+			"attempts": game.guesses_number(),
+			"guesses": game.get_guesses(),
+			"mode": game.mode(),
+			"difficult": game.difficult(),
+			"pack": game.pack(),
+			"date": game.date(),
+			"puzzle": game.puzzle(),
+			"player": game.player(),
+			"played": game.played(),
+		})
+	}).collect();
+	let mut summary = gamejson.to_stats().to_json();
+	summary["games"] = json!(games.len());
+	json!({ "games": games, "summary": summary })
+}
+
+/// Quote a field that contains a comma, a quote or a line break
+fn csv_field(field: &str) -> String {
+	if field.contains([',', '"', '\n']) {
+		format!("\"{}\"", field.replace('"', "\"\""))
+	}
+	else{
+		String::from(field)
+	}
+}
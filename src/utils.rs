@@ -14,28 +14,119 @@ pub fn clear_command_screen() -> (){
 
 use console;
 
-pub fn print_with_color(ch: char, color: char) -> (){
+pub fn print_with_color(ch: &str, color: char) -> (){
 	match color{
-		'G' => print!("{}", console::style(format!("{}", ch)).bold().green()),
-		'R' => print!("{}", console::style(format!("{}", ch)).bold().red()),
-		'Y' => print!("{}", console::style(format!("{}", ch)).bold().yellow()),
-		'X' => print!("{}", console::style(format!("{}", ch)).bold().white()),
+		'G' => print!("{}", console::style(ch).bold().green()),
+		'R' => print!("{}", console::style(ch).bold().red()),
+		'Y' => print!("{}", console::style(ch).bold().yellow()),
+		'X' => print!("{}", console::style(ch).bold().white()),
 		_ => assert!(false)
 	}
 }
-use std::{collections::HashSet, fs::{read_to_string}};
+use std::{collections::{HashMap, HashSet}, fs::{read_to_string}};
+use unicode_normalization::UnicodeNormalization;
+use unicode_segmentation::UnicodeSegmentation;
+
+/// Split a word into user-perceived letters (grapheme clusters), so that accented
+/// letters written with combining marks count as a single letter.
+pub fn split_letters(word: &str) -> Vec<String> {
+	word.nfc().collect::<String>().graphemes(true).map(String::from).collect()
+}
+
+/// Upper-case a word without knowing its alphabet.
+pub fn normalize_word(word: &str) -> String {
+	word.trim().nfc().collect::<String>().to_uppercase()
+}
+
+/// The letters a word pack is written in, in keyboard display order.
+pub struct Alphabet {
+	letters: Vec<String>,
+	index: HashMap<String, usize>,
+}
+
+impl Alphabet {
+	pub fn new(letters: &str) -> Alphabet {
+		let mut result: Vec<String> = Vec::new();
+		for letter in split_letters(letters) {
+			let letter = upper_letter(&letter).unwrap_or(letter);
+			if !result.contains(&letter) {
+				result.push(letter);
+			}
+		}
+		let index = result.iter().enumerate().map(|(i, letter)| (letter.clone(), i)).collect();
+		Alphabet { letters: result, index }
+	}
+
+	pub fn len(&self) -> usize {
+		self.letters.len()
+	}
+
+	pub fn is_empty(&self) -> bool {
+		self.letters.is_empty()
+	}
+
+	pub fn letters(&self) -> &[String] {
+		&self.letters
+	}
+
+	pub fn index_of(&self, letter: &str) -> Option<usize> {
+		self.index.get(letter).copied()
+	}
+
+	/// Upper-case every letter whose upper-case form is still part of the alphabet,
+	/// so that e.g. `ß` survives even though it upper-cases to `SS`.
+	pub fn normalize(&self, word: &str) -> String {
+		let word = word.trim();
+		if word.is_ascii() {
+			// fast path: ASCII letters never combine, so every char is a letter
+			return word.chars().map(|ch| {
+				let upper = ch.to_ascii_uppercase();
+				if self.index_of(upper.encode_utf8(&mut [0; 4])).is_some() { upper } else { ch }
+			}).collect();
+		}
+		let mut result = String::new();
+		for letter in split_letters(word.trim()) {
+			match upper_letter(&letter) {
+				Some(upper) if self.index_of(&upper).is_some() => result.push_str(&upper),
+				_ => result.push_str(&letter),
+			}
+		}
+		result
+	}
+
+	/// The alphabet index of every letter of `word`, or `None` if a letter is not in the alphabet.
+	pub fn indices(&self, word: &str) -> Option<Vec<usize>> {
+		if word.is_ascii() {
+			return word.chars().map(|ch| self.index_of(ch.encode_utf8(&mut [0; 4]))).collect();
+		}
+		split_letters(word).iter().map(|letter| self.index_of(letter)).collect()
+	}
+}
+
+fn upper_letter(letter: &str) -> Option<String> {
+	let upper: String = letter.to_uppercase().nfc().collect();
+	match upper.graphemes(true).count() {
+		1 => Some(upper),
+		_ => None,
+	}
+}
+
 pub struct WordDict {
 	pub final_list: Vec<String>, 
 	acceptable_list: Vec<String>,
 	final_set: HashSet<String>, 
-	acceptable_set: HashSet<String>
+	acceptable_set: HashSet<String>,
+	pub length: usize,
+	pub alphabet: Alphabet,
 }
 
-use crate::builtin_words;
+use crate::word_pack::WordPack;
+use crate::shuffle::{answer_order, ShuffleVersion};
 
 impl WordDict {
 	pub fn new() -> WordDict {
-		WordDict { final_list: Vec::new(), acceptable_list: Vec::new(), final_set: HashSet::new(), acceptable_set: HashSet::new() }
+		WordDict { final_list: Vec::new(), acceptable_list: Vec::new(), final_set: HashSet::new(), acceptable_set: HashSet::new(),
+			length: 0, alphabet: Alphabet::new("") }
 	}
 
 	pub fn vaild(&self, user_input: &str) -> bool {	
@@ -43,63 +134,124 @@ impl WordDict {
 		return self.acceptable_set.contains(&String::from(user_input));
 	}
 
-	pub fn build(&mut self, final_address: Option<String>, acceptable_address: Option<String>) -> Result<(), Box<dyn std::error::Error>> {
-		if acceptable_address.is_none() {
-			for val in builtin_words::ACCEPTABLE {
-				self.acceptable_list.push(String::from(*val).to_ascii_uppercase());
-			}
+	pub fn is_final(&self, word: &str) -> bool {
+		self.final_set.contains(word)
+	}
+
+	pub fn acceptable_list(&self) -> &[String] {
+		&self.acceptable_list
+	}
+
+	/// Load the lists of `pack`, `final_address` and `acceptable_address` replace the pack's own lists.
+	pub fn load(&mut self, pack: &WordPack, final_address: Option<String>, acceptable_address: Option<String>) -> Result<(), Box<dyn std::error::Error>> {
+		self.length = pack.length;
+		self.alphabet = Alphabet::new(&pack.alphabet);
+		self.acceptable_list = match acceptable_address {
+			Some(address) => load_word_file(&address)?,
+			None => pack.acceptable_list.clone(),
+		};
+		self.final_list = match final_address {
+			Some(address) => load_word_file(&address)?,
+			None => pack.final_list.clone(),
+		};
+		self.acceptable_list = self.acceptable_list.iter().map(|word| self.alphabet.normalize(word)).collect();
+		self.final_list = self.final_list.iter().map(|word| self.alphabet.normalize(word)).collect();
+		self.final_set = self.final_list.clone().into_iter().collect::<HashSet<String>>();
+		self.acceptable_set = self.acceptable_list.clone().into_iter().collect::<HashSet<String>>();
+		Ok(())
+	}
+
+	pub fn build(&mut self, pack: &WordPack, final_address: Option<String>, acceptable_address: Option<String>) -> Result<(), Box<dyn std::error::Error>> {
+		self.load(pack, final_address, acceptable_address)?;
+		match self.check().into_iter().next() {
+			Some(problem) => Err(problem.into()),
+			None => Ok(()),
 		}
-		else{
-			let address = acceptable_address.unwrap();
-			for line in read_to_string(address).unwrap().lines(){
-				let word = line.trim().to_ascii_uppercase();
-				self.acceptable_list.push(word);
-			}
-			
+	}
+
+	/// Walk `order` (indices into `final_list`) from `idx` and return the first answer not in
+	/// `played`, together with the position to continue from. `None` once the pool is exhausted.
+	pub fn next_answer(&self, order: &[usize], idx: usize, played: &HashSet<String>) -> Option<(String, usize)> {
+		order.iter().enumerate().skip(idx)
+			.map(|(i, id)| (&self.final_list[*id], i))
+			.find(|(word, _)| !played.contains(*word))
+			.map(|(word, i)| (word.clone(), i + 1))
+	}
+
+	/// The answer of day `day` (from 1) in the order `seed` gives.
+	pub fn day_answer(&self, seed: u64, day: usize, version: ShuffleVersion) -> Result<String, Box<dyn std::error::Error>> {
+		let len = self.final_list.len();
+		let order = answer_order(len, seed, version);
+		match day.checked_sub(1).and_then(|idx| order.get(idx)) {
+			Some(id) => Ok(self.final_list[*id].clone()),
+			None => Err(format!("day {} is outside the {} answers", day, len).into()),
 		}
-		if final_address.is_none() {
-			for val in builtin_words::FINAL {
-				self.final_list.push(String::from(*val).to_ascii_uppercase());
+	}
+
+	/// Run every validation on the loaded lists and collect all the problems found.
+	pub fn check(&self) -> Vec<String> {
+		let mut problems = Vec::new();
+		for (name, list) in [("final", &self.final_list), ("acceptable", &self.acceptable_list)] {
+			if list.is_empty() {
+				problems.push(format!("{} list is empty", name));
 			}
-		}
-		else{
-			let address = final_address.unwrap();
-			for line in read_to_string(address).unwrap().lines(){
-				let word = line.trim().to_ascii_uppercase();
-				self.final_list.push(word);
+			for word in repeated_words(list) {
+				problems.push(format!("{} list: {} is repeated", name, word));
+			}
+			for word in list {
+				if word.chars().count() != self.length && split_letters(word).len() != self.length {
+					problems.push(format!("{} list: {:?} does not have {} letters", name, word, self.length));
+				}
+				else if self.alphabet.indices(word).is_none() {
+					problems.push(format!("{} list: {:?} contains letters outside the alphabet", name, word));
+				}
 			}
 		}
-		self.final_set = self.final_list.clone().into_iter().collect::<HashSet<String>>();
-		self.acceptable_set = self.acceptable_list.clone().into_iter().collect::<HashSet<String>>();
-		if self.acceptable_list.len() != self.acceptable_set.len() {
-			Err("acceptable words table have REPEATED words.".into())
-		}
-		else if self.final_list.len() != self.final_set.len() {
-			Err("final words table have REPEATED words.".into())
-		}
-		else if !self.final_set.is_subset(&self.acceptable_set){
-			Err("final set is not a subset of acceptable set".into())
+		let mut missing: Vec<&String> = self.final_set.difference(&self.acceptable_set).collect();
+		missing.sort();
+		for word in missing {
+			problems.push(format!("final list: {} is not in the acceptable list", word));
 		}
-		else{
-			Ok(())
+		problems
+	}
+}
+
+/// Read a word list, one word per line, skipping blank lines. Words are left as written,
+/// `WordDict::load` upper-cases them with the pack's alphabet.
+pub fn load_word_file(address: &str) -> Result<Vec<String>, Box<dyn std::error::Error>> {
+	let content = read_to_string(address).map_err(|err| format!("cannot read {}: {}", address, err))?;
+	Ok(content.lines().map(|line| String::from(line.trim())).filter(|word| !word.is_empty()).collect())
+}
+
+fn repeated_words(list: &[String]) -> Vec<String> {
+	let mut seen = HashSet::new();
+	let mut repeated = Vec::new();
+	for word in list {
+		if !seen.insert(word) && !repeated.contains(word) {
+			repeated.push(word.clone());
 		}
 	}
+	repeated
 }
 
-use std::collections::HashMap;
 pub struct Stats{
     wins: i32,
     total: i32,
     attempts: i32,
     buffer: i32,
     guess_history: HashMap<String, i32>,
+    /// Number of favorite words listed
+    top: usize,
 }
 
+/// Favorite words listed unless `--top` says otherwise
+pub const DEFAULT_TOP: usize = 5;
+
 impl Stats{
     pub fn new(wins: Option<i32>, total: Option<i32>, attempts: Option<i32>, buffer: Option<i32>, guess_history: Option<HashMap<String, i32>>) -> Stats {
         Stats { wins: wins.unwrap_or(0), total: total.unwrap_or(0),
 			 attempts: attempts.unwrap_or(0), buffer: buffer.unwrap_or(0), 
-			 guess_history: guess_history.unwrap_or(HashMap::new()) }
+			 guess_history: guess_history.unwrap_or(HashMap::new()), top: DEFAULT_TOP }
     }
 
     pub fn add_game(&mut self, result: bool){
@@ -125,24 +277,40 @@ impl Stats{
         }
     }
 
-    fn get_top5_words(&self) -> Vec<(i32, String)> {
+    pub fn set_top(&mut self, top: usize) {
+        self.top = top;
+    }
+
+    /// The most used words, ties in alphabetical order
+    fn get_top_words(&self) -> Vec<(i32, String)> {
         let mut result: Vec<(i32, String)> = Vec::new();
         for (key, value) in &self.guess_history {
             result.push((-*value, key.clone()));
         }
         result.sort();
-        while result.len() > 5 {
-            result.pop();
-        }
+        result.truncate(self.top);
         result
     }
 
+    /// Wins, losses, average attempts and the top words as JSON
+    pub fn to_json(&self) -> serde_json::Value {
+        let top_words: Vec<serde_json::Value> = self.get_top_words().into_iter()
+            .map(|(count, word)| serde_json::json!({ "word": word, "count": -count }))
+            .collect();
+        serde_json::json!({
+            "wins": self.wins,
+            "losses": self.total - self.wins,
+            "average_attempts": self.att_rate(),
+            "top_words": top_words,
+        })
+    }
+
     pub fn print_result(&self, is_tty: bool){
-        let vec = self.get_top5_words();
+        let vec = self.get_top_words();
         if is_tty {
             println!("Your Game Performance:");
             println!("Win: {}, Lose: {}, Average attempts in win game: {:.2}", self.wins, self.total - self.wins, self.att_rate());
-            println!("Your TOP5 favorite words:");
+            println!("Your TOP{} favorite words:", self.top);
             for i in 0..vec.len() {
                 let (count, user_input) = &vec[i];
                 println!("Rank {}: {}, used {} times.", i+1, user_input.to_ascii_uppercase(), -count);
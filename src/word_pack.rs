@@ -0,0 +1,101 @@
+// Word packs: a named set of final and acceptable words for one language
+
+use serde::{Deserialize, Serialize};
+use std::{fs::{self, read_to_string}, path::{Path, PathBuf}};
+use crate::builtin_words;
+
+pub const DEFAULT_PACK: &str = "en";
+const LATIN_ALPHABET: &str = "ABCDEFGHIJKLMNOPQRSTUVWXYZ";
+const SPANISH_ALPHABET: &str = "ABCDEFGHIJKLMNÑOPQRSTUVWXYZ";
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct WordPack {
+	pub name: String,
+	pub language: String,
+	/// Every letter a word may use, in keyboard display order
+	pub alphabet: String,
+	pub length: usize,
+	#[serde(rename = "final")]
+	pub final_list: Vec<String>,
+	#[serde(rename = "acceptable")]
+	pub acceptable_list: Vec<String>,
+}
+
+impl WordPack {
+	fn bundled(name: &str, language: &str, alphabet: &str, final_list: &[&str], acceptable_list: &[&str]) -> WordPack {
+		WordPack {
+			name: String::from(name),
+			language: String::from(language),
+			alphabet: String::from(alphabet),
+			length: 5,
+			final_list: final_list.iter().map(|val| val.to_uppercase()).collect(),
+			acceptable_list: acceptable_list.iter().map(|val| val.to_uppercase()).collect(),
+		}
+	}
+
+	fn validate(self) -> Result<WordPack, Box<dyn std::error::Error>> {
+		if self.length == 0 {
+			return Err(format!("pack {}: word length must be positive", self.name).into());
+		}
+		if self.alphabet.trim().is_empty() {
+			return Err(format!("pack {}: alphabet is empty", self.name).into());
+		}
+		Ok(self)
+	}
+}
+
+pub fn bundled_packs() -> Vec<WordPack> {
+	vec![
+		WordPack::bundled("en", "English", LATIN_ALPHABET, builtin_words::FINAL, builtin_words::ACCEPTABLE),
+		WordPack::bundled("de", "Deutsch", LATIN_ALPHABET, builtin_words::GERMAN_FINAL, builtin_words::GERMAN_ACCEPTABLE),
+		WordPack::bundled("es", "Español", SPANISH_ALPHABET, builtin_words::SPANISH_FINAL, builtin_words::SPANISH_ACCEPTABLE),
+	]
+}
+
+/// The directory user-installed packs are read from: `--pack-dir`, then `$WORDLE_PACK_DIR`,
+/// then `~/.wordle/packs`.
+pub fn pack_dir(from_args: Option<String>) -> Option<PathBuf> {
+	from_args.or_else(|| std::env::var("WORDLE_PACK_DIR").ok())
+		.map(PathBuf::from)
+		.or_else(|| std::env::var("HOME").ok().map(|home| PathBuf::from(home).join(".wordle").join("packs")))
+}
+
+/// Look a pack up by name, bundled packs first, then `<pack_dir>/<name>.json`.
+pub fn find(name: &str, dir: Option<&Path>) -> Result<WordPack, Box<dyn std::error::Error>> {
+	if let Some(pack) = bundled_packs().into_iter().find(|pack| pack.name == name) {
+		return Ok(pack);
+	}
+	if let Some(dir) = dir {
+		let address = dir.join(format!("{}.json", name));
+		if address.is_file() {
+			return read_pack(&address);
+		}
+	}
+	Err(format!("word pack {} not found", name).into())
+}
+
+/// Every pack that can be selected with `--pack`. Broken user packs are reported on stderr.
+pub fn list(dir: Option<&Path>) -> Vec<WordPack> {
+	let mut packs = bundled_packs();
+	let mut addresses: Vec<PathBuf> = match dir.map(fs::read_dir) {
+		Some(Ok(entries)) => entries.filter_map(|entry| entry.ok().map(|entry| entry.path())).collect(),
+		_ => Vec::new(),
+	};
+	addresses.sort();
+	for address in addresses {
+		if address.extension().and_then(|ext| ext.to_str()) != Some("json") {
+			continue;
+		}
+		match read_pack(&address) {
+			Ok(pack) => packs.push(pack),
+			Err(err) => eprintln!("skipping {}: {}", address.display(), err),
+		}
+	}
+	packs
+}
+
+fn read_pack(address: &Path) -> Result<WordPack, Box<dyn std::error::Error>> {
+	let raw_json = read_to_string(address)?;
+	let pack: WordPack = serde_json::from_str(&raw_json)?;
+	pack.validate()
+}
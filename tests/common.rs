@@ -66,6 +66,9 @@ lazy_static! {
     static ref EXE_PATH: PathBuf = build_and_find_path("wordle");
 }
 
+/// The clock of every run, so the played time saved with each game is known
+pub const NOW: &str = "2024-01-15T09:30:00+08:00";
+
 pub struct TestCase {
     name: String,
     arguments: Vec<String>,
@@ -100,6 +103,7 @@ impl TestCase {
     fn execute_program_and_feed_input(&self) -> Child {
         let mut command = Command::new(EXE_PATH.as_os_str())
             .args(&self.arguments)
+            .env("WORDLE_NOW", NOW)
             .stdin(Stdio::piped())
             .stdout(Stdio::piped())
             .spawn()
@@ -169,6 +173,20 @@ impl TestCase {
         assert_json_eq!(run_state, answer_state);
     }
 
+    // not every test file compares the output of a failing run
+    #[allow(dead_code)]
+    pub fn run_and_compare_failure(&self) {
+        let command = self.execute_program_and_feed_input();
+        let output = command.wait_with_output().expect("failed to wait on process");
+        assert!(!output.status.success(), "case {} should exit with error", self.name);
+        assert_eq!(
+            String::from_utf8(output.stdout).unwrap().trim(),
+            self.answer.trim(),
+            "case {} incorrect",
+            self.name
+        );
+    }
+
     pub fn run_and_expect_exit(&self) {
         let command = self.execute_program_and_feed_input();
         assert!(
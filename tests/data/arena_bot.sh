@@ -0,0 +1,14 @@
+#!/bin/sh
+# Arena test solver: guesses the words given as arguments in order, from the first one
+# again in every game, and empty lines once they run out. A single word is repeated.
+words="$*"
+n=0
+while read -r line; do
+    case "$line" in
+        END) exit 0 ;;
+        CORRECT*|FAILED*) continue ;;
+        START*) n=0 ;;
+    esac
+    n=$((n + 1))
+    echo "$words" | cut -d ' ' -f "$n"
+done
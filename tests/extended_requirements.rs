@@ -0,0 +1,226 @@
+use ntest::timeout;
+
+mod common;
+use common::TestCase;
+
+#[test]
+#[timeout(2000)]
+fn test_09_dict_tools() {
+    // compare two word lists
+    TestCase::read("09_01_dict_diff").run_and_compare_result();
+    // check reports the one word of the final list missing from the acceptable list
+    TestCase::read("09_02_dict_check_invalid").run_and_compare_failure();
+    // merged lists are deduplicated regardless of case and spacing, sorted, and written to -o if given
+    TestCase::read("09_03_dict_merge").run_and_compare_result();
+    let _ = std::fs::remove_file("target/09_04_dict_merge_output.txt");
+    TestCase::read("09_04_dict_merge_output").run_and_compare_result();
+    assert_eq!(
+        std::fs::read_to_string("target/09_04_dict_merge_output.txt").unwrap(),
+        std::fs::read_to_string("tests/cases/09_03_dict_merge.ans").unwrap()
+    );
+    // length histogram, letter and positional frequencies of a list of mixed lengths
+    TestCase::read("09_05_dict_stats").run_and_compare_result();
+    // both lists of an installed pack, then every pack that can be selected
+    TestCase::read("09_06_dict_export_final").run_and_compare_result();
+    TestCase::read("09_07_dict_export_acceptable").run_and_compare_result();
+    TestCase::read("09_08_dict_packs").run_and_compare_result();
+    // a final list without words is a problem of its own
+    TestCase::read("09_09_dict_check_empty").run_and_compare_failure();
+}
+
+#[test]
+#[timeout(2000)]
+fn test_10_word_packs() {
+    // play with the bundled German pack
+    TestCase::read("10_01_german_pack").run_and_compare_result();
+    // the Spanish pack uses Ñ, which is outside A-Z
+    TestCase::read("10_02_spanish_pack").run_and_compare_result();
+    // a pack installed as JSON in the pack directory, with its own alphabet
+    TestCase::read("10_03_installed_pack").run_and_compare_result();
+}
+
+#[test]
+#[timeout(2000)]
+fn test_11_daily_mode() {
+    // play the daily puzzle of a fixed date and record it
+    TestCase::read("11_01_daily_mode").run_and_compare_game_state();
+    // the same daily puzzle cannot be played twice
+    TestCase::read("11_02_daily_replayed").run_and_expect_exit();
+    // the daily puzzle of the same date in another word pack is a different puzzle
+    TestCase::read("11_03_daily_other_pack").run_and_compare_game_state();
+    // there is no daily puzzle without final words
+    TestCase::read("11_04_daily_empty_final").run_and_compare_failure();
+}
+
+#[test]
+#[timeout(2000)]
+fn test_12_no_repeat() {
+    // answers already in the state file are skipped
+    TestCase::read("12_01_no_repeat").run_and_compare_game_state();
+    // every answer has been played
+    TestCase::read("12_02_no_repeat_exhausted").run_and_expect_exit();
+    // only answers of the same word pack are skipped, the English PROBE does not rule out the German one
+    TestCase::read("12_03_no_repeat_other_pack").run_and_compare_game_state();
+}
+
+#[test]
+#[timeout(3000)]
+fn test_13_stable_seed_mapping() {
+    // golden answers for several seeds, these must never change
+    TestCase::read("13_01_stable_seed").run_and_compare_result();
+    TestCase::read("13_02_stable_seed").run_and_compare_result();
+    TestCase::read("13_03_stable_seed").run_and_compare_result();
+    // default seed
+    TestCase::read("13_04_stable_seed").run_and_compare_result();
+}
+
+#[test]
+#[timeout(1000)]
+fn test_14_share_grid() {
+    // difficult mode game with the emoji grid printed at the end
+    TestCase::read("14_01_share_grid").run_and_compare_result();
+}
+
+#[test]
+#[timeout(2000)]
+fn test_15_challenge_codes() {
+    // play the word hidden in a challenge code
+    TestCase::read("15_01_challenge_code").run_and_compare_result();
+    // a code with a wrong checksum is rejected
+    TestCase::read("15_02_damaged_challenge_code").run_and_expect_exit();
+}
+
+#[test]
+#[timeout(3000)]
+fn test_16_reverse_mode() {
+    // the program guesses SHINE from the feedback
+    TestCase::read("16_01_reverse_mode").run_and_compare_result();
+    // feedback that no answer can produce
+    TestCase::read("16_02_reverse_inconsistent").run_and_expect_exit();
+}
+
+#[test]
+#[timeout(2000)]
+fn test_17_two_player() {
+    // two rounds with alternating setters, the match score is saved in the state
+    TestCase::read("17_01_two_player").run_and_compare_game_state();
+}
+
+#[test]
+#[timeout(3000)]
+fn test_20_jsonl_protocol() {
+    // a bot plays a game through JSON commands, finished games go to the state file
+    TestCase::read("20_01_jsonl_protocol").run_and_compare_game_state();
+    // with a signing key the statistics leave the unsigned game out and say how the history verified
+    TestCase::read("20_02_jsonl_signed_stats").run_and_compare_game_state();
+}
+
+#[test]
+#[timeout(5000)]
+fn test_21_arena() {
+    // scripted solvers, one that never answers and one that only sends invalid guesses
+    TestCase::read("21_01_arena").run_and_compare_result();
+}
+
+#[test]
+#[timeout(3000)]
+fn test_22_replay() {
+    // the latest game with an answer, with the solver's view before each guess
+    TestCase::read("22_01_replay_annotated").run_and_compare_game_state();
+    // a daily puzzle picked by its date
+    TestCase::read("22_02_replay_by_date").run_and_compare_game_state();
+}
+
+#[test]
+#[timeout(3000)]
+fn test_23_guess_analysis() {
+    // skill and luck of each guess of a lost game from the state file
+    TestCase::read("23_01_analyze_saved_game").run_and_compare_game_state();
+    TestCase::read("23_02_analyze_better_guess").run_and_compare_game_state();
+}
+
+#[test]
+#[timeout(2000)]
+fn test_24_stats_export() {
+    // one CSV row per game and a summary table, fields with commas are quoted
+    TestCase::read("24_01_stats_export_csv").run_and_compare_game_state();
+    // the same as a JSON document
+    TestCase::read("24_02_stats_export_json").run_and_compare_game_state();
+}
+
+#[test]
+#[timeout(2000)]
+fn test_25_filtered_stats() {
+    // hard-mode daily puzzles of January
+    TestCase::read("25_01_stats_filter_dates").run_and_compare_game_state();
+    // games of another word pack
+    TestCase::read("25_02_stats_filter_pack").run_and_compare_game_state();
+    // games one player won, exported
+    TestCase::read("25_03_stats_filter_player").run_and_compare_game_state();
+    // games of the last days by the day they were played, daily puzzles of older histories by their date
+    TestCase::read("25_04_stats_filter_played").run_and_compare_game_state();
+}
+
+#[test]
+#[timeout(2000)]
+fn test_26_letter_stats() {
+    // double letters, first letters, letters reused after being absent, positions turning green
+    TestCase::read("26_01_letter_stats").run_and_compare_game_state();
+}
+
+#[test]
+#[timeout(2000)]
+fn test_27_top_words() {
+    // the three favorite words of the saved games, ties in alphabetical order
+    TestCase::read("27_01_stats_show_top").run_and_compare_game_state();
+    // opening words with their win rate and average guesses in won games
+    TestCase::read("27_02_stats_openers").run_and_compare_game_state();
+    // --top also sets the length of the list printed after each game
+    TestCase::read("27_03_game_top").run_and_compare_game_state();
+}
+
+#[test]
+#[timeout(2000)]
+fn test_28_achievements() {
+    // achievements computed again from a state file that never stored them
+    TestCase::read("28_01_stats_achievements").run_and_compare_game_state();
+    // the achievements earned so far are stored after the first game
+    TestCase::read("28_02_save_achievements").run_and_compare_game_state();
+}
+
+#[test]
+#[timeout(2000)]
+fn test_30_state_merge() {
+    // a team member's history merged into the state file: the repeated game is dropped,
+    // the rounds are counted again and the differing daily puzzle and score are reported
+    TestCase::read("30_01_state_merge").run_and_compare_game_state();
+    // a game copied with another signature is the same game, one played at another time is not
+    TestCase::read("30_02_state_merge_same_game").run_and_compare_game_state();
+    // signed histories are left alone without the key, which would sign the merged games again
+    TestCase::read("30_03_state_merge_without_key").run_and_compare_failure();
+}
+
+#[test]
+#[timeout(2000)]
+fn test_31_state_check() {
+    // every kind of problem is reported and the state file is left as it is
+    TestCase::read("31_01_state_check").run_and_compare_game_state();
+    // empty games are dropped, extra guesses cut and the rounds counted again
+    TestCase::read("31_02_state_repair").run_and_compare_game_state();
+}
+
+#[test]
+#[timeout(2000)]
+fn test_32_signed_state() {
+    // the new game is signed, the game saved without a key stays unsigned
+    TestCase::read("32_01_sign_games").run_and_compare_game_state();
+    // stats check the chain and report on the signed games only
+    TestCase::read("32_02_verify_signed").run_and_compare_game_state();
+    // a signed game edited afterwards no longer verifies and is not counted
+    TestCase::read("32_03_verify_tampered").run_and_compare_game_state();
+    // the game cut short by a repair is signed again with the key
+    TestCase::read("32_04_repair_signed").run_and_compare_game_state();
+    TestCase::read("32_05_repair_without_key").run_and_compare_failure();
+    // the statistics printed while playing count only the signed games that verify
+    TestCase::read("32_06_play_stats_tampered").run_and_compare_game_state();
+}
@@ -0,0 +1,177 @@
+use ntest::timeout;
+use pretty_assertions::assert_eq;
+use serde_json::{json, Value};
+use std::fs;
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::TcpStream;
+use std::process::{Child, ChildStdout, Command, Stdio};
+
+// only the clock of the test cases is used here
+#[allow(dead_code)]
+mod common;
+use common::NOW;
+
+/// Killed when dropped, so a failing test does not leave it running
+struct Server {
+    process: Child,
+    output: BufReader<ChildStdout>,
+    address: String,
+}
+
+impl Drop for Server {
+    fn drop(&mut self) {
+        let _ = self.process.kill();
+        let _ = self.process.wait();
+    }
+}
+
+fn start_server(args: &[&str]) -> Server {
+    let mut process = Command::new(env!("CARGO_BIN_EXE_wordle"))
+        .args(args)
+        .env("WORDLE_NOW", NOW)
+        .stdout(Stdio::piped())
+        .spawn()
+        .unwrap();
+    let mut output = BufReader::new(process.stdout.take().unwrap());
+    let mut line = String::new();
+    output.read_line(&mut line).unwrap();
+    let address = line.trim().strip_prefix("LISTENING ").expect("server did not print its address");
+    let address = String::from(address);
+    Server { process, output, address }
+}
+
+struct Client {
+    reader: BufReader<TcpStream>,
+    stream: TcpStream,
+}
+
+impl Client {
+    fn connect(address: &str, name: &str) -> Client {
+        let stream = TcpStream::connect(address).unwrap();
+        let mut client = Client { reader: BufReader::new(stream.try_clone().unwrap()), stream };
+        client.send(name);
+        client
+    }
+
+    fn send(&mut self, line: &str) {
+        writeln!(self.stream, "{}", line).unwrap();
+    }
+
+    fn expect(&mut self, lines: &[&str]) {
+        for expected in lines {
+            let mut line = String::new();
+            self.reader.read_line(&mut line).unwrap();
+            assert_eq!(line.trim_end(), *expected);
+        }
+    }
+}
+
+#[test]
+#[timeout(5000)]
+fn test_18_race_server() {
+    let mut server = start_server(&["-w", "crane", "serve", "--port", "0", "--players", "2"]);
+    // a client that never sends its name does not hold up the others
+    let _silent = TcpStream::connect(&server.address).unwrap();
+    let mut ann = Client::connect(&server.address, "Ann");
+    ann.expect(&["WELCOME Ann"]);
+    // a name can only be taken once
+    let mut copy = Client::connect(&server.address, "Ann");
+    copy.expect(&["TAKEN Ann"]);
+    let mut bob = Client::connect(&server.address, "bob");
+    bob.expect(&["WELCOME bob"]);
+    ann.expect(&["START 5 6"]);
+    bob.expect(&["START 5 6"]);
+
+    // same replies as the non-TTY mode
+    ann.send("xxxxx");
+    ann.expect(&["INVALID"]);
+    ann.send("slate");
+    ann.expect(&["RRGRG GXXXGXXXXXXRXXXXXXRRXXXXXX"]);
+    ann.send("crane");
+    ann.expect(&["GGGGG GXGXGXXXXXXRXGXXXGRRXXXXXX", "CORRECT 2", "RANK 1"]);
+
+    // fewer guesses, but solved later
+    bob.send("crane");
+    bob.expect(&["GGGGG GXGXGXXXXXXXXGXXXGXXXXXXXX", "CORRECT 1", "RANK 2"]);
+
+    assert!(server.process.wait().unwrap().success());
+    let mut scoreboard = String::new();
+    server.output.read_to_string(&mut scoreboard).unwrap();
+    assert_eq!(
+        scoreboard,
+        "JOINED Ann\nJOINED bob\nSCOREBOARD\n1 Ann CORRECT 2\nSCOREBOARD\n1 Ann CORRECT 2\n2 bob CORRECT 1\n"
+    );
+}
+
+fn http(address: &str, method: &str, path: &str, body: &str) -> (u16, Value) {
+    let mut stream = TcpStream::connect(address).unwrap();
+    write!(
+        stream,
+        "{} {} HTTP/1.1\r\nHost: {}\r\nConnection: close\r\nContent-Length: {}\r\n\r\n{}",
+        method,
+        path,
+        address,
+        body.len(),
+        body
+    )
+    .unwrap();
+    let mut response = String::new();
+    stream.read_to_string(&mut response).unwrap();
+    let status = response.split(' ').nth(1).unwrap().parse().unwrap();
+    let (_, body) = response.split_once("\r\n\r\n").unwrap();
+    (status, serde_json::from_str(body).unwrap())
+}
+
+#[test]
+#[timeout(5000)]
+fn test_19_http_api() {
+    let state = "target/19_http_api_state.json";
+    let _ = fs::remove_file(state);
+    let server = start_server(&["--state", state, "http", "--port", "0"]);
+    let address = &server.address;
+
+    // seed 0, day 1 is VAGUE
+    let (status, board) = http(address, "POST", "/games", r#"{"seed": 0, "day": 1}"#);
+    assert_eq!(status, 201);
+    assert_eq!(board["status"], "running");
+    assert_eq!(board["length"], 5);
+    assert_eq!(board.get("answer"), None);
+
+    let (status, board) = http(address, "POST", "/games/1/guesses", r#"{"guess": "value"}"#);
+    assert_eq!(status, 200);
+    assert_eq!(board["guesses"], json!([{ "word": "VALUE", "result": "GGRGG" }]));
+    assert_eq!(board.get("answer"), None);
+    let (status, _) = http(address, "POST", "/games/1/guesses", r#"{"guess": "xxxxx"}"#);
+    assert_eq!(status, 400);
+    let (status, board) = http(address, "GET", "/games/1", "");
+    assert_eq!(status, 200);
+    assert_eq!(board["guesses"].as_array().unwrap().len(), 1);
+    assert_eq!(board.get("answer"), None);
+
+    let (status, board) = http(address, "POST", "/games/1/guesses", r#"{"guess": "vague"}"#);
+    assert_eq!(status, 200);
+    assert_eq!(board["status"], "won");
+    assert_eq!(board["answer"], "VAGUE");
+    let (status, _) = http(address, "POST", "/games/1/guesses", r#"{"guess": "vague"}"#);
+    assert_eq!(status, 409);
+    let (status, _) = http(address, "GET", "/games/2", "");
+    assert_eq!(status, 404);
+
+    let (status, stats) = http(address, "GET", "/stats", "");
+    assert_eq!(status, 200);
+    assert_eq!(stats["wins"], 1);
+    assert_eq!(stats["losses"], 0);
+    drop(server);
+
+    let saved: Value = serde_json::from_str(&fs::read_to_string(state).unwrap()).unwrap();
+    assert_eq!(saved, json!({ "total_rounds": 1, "games": [{ "answer": "VAGUE", "guesses": ["VALUE", "VAGUE"], "played": NOW }] }));
+
+    // with a signing key the unsigned and the tampered game are not counted, nothing is played
+    // so the history is only read
+    let server = start_server(&["--state", "tests/cases/32_03_verify_tampered.before.json",
+        "--sign-key", "tests/data/sign.key", "http", "--port", "0"]);
+    let (status, stats) = http(&server.address, "GET", "/stats", "");
+    assert_eq!(status, 200);
+    assert_eq!(stats["wins"], 0);
+    assert_eq!(stats["verification"], json!({ "verified": false, "signed": 0, "unsigned": 1, "broken": 2 }));
+}
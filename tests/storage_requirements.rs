@@ -0,0 +1,64 @@
+use ntest::timeout;
+use pretty_assertions::assert_eq;
+use std::fs;
+use std::io::Write;
+use std::process::{Command, Stdio};
+
+// only the clock of the test cases is used here
+#[allow(dead_code)]
+mod common;
+use common::NOW;
+
+/// Run the program with `input` on stdin and return its stdout
+fn run(args: &[&str], input: &str) -> String {
+    let mut process = Command::new(env!("CARGO_BIN_EXE_wordle"))
+        .args(args)
+        .env("WORDLE_NOW", NOW)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()
+        .unwrap();
+    process.stdin.take().unwrap().write_all(input.as_bytes()).unwrap();
+    let output = process.wait_with_output().unwrap();
+    assert!(output.status.success(), "wordle {:?} should exit normally", args);
+    String::from_utf8(output.stdout).unwrap()
+}
+
+/// Two games saved by separate runs, then the statistics read back
+fn play_two_games(state: &str) -> String {
+    run(&["-w", "crane", "--achievements", "-S", state], "slate\ncrane\n");
+    run(&["-w", "hello", "-S", state], "hello\n");
+    run(&["stats", "show", "-S", state], "")
+}
+
+#[test]
+#[timeout(4000)]
+fn test_29_history_stores() {
+    // the JSON-lines log only grows, one record per game or achievement
+    let log = "target/29_history_store.jsonl";
+    let _ = fs::remove_file(log);
+    let stats = play_two_games(&format!("jsonl:{}", log));
+    assert_eq!(stats.trim(), "2 0 1.50\nCRANE 1 HELLO 1 SLATE 1");
+    let records: Vec<String> = fs::read_to_string(log).unwrap().lines().map(String::from).collect();
+    assert_eq!(records, [
+        r#"{"game":{"answer":"CRANE","guesses":["SLATE","CRANE"],"played":"2024-01-15T09:30:00+08:00"}}"#,
+        r#"{"achievement":"first_win"}"#,
+        r#"{"achievement":"two_guesses"}"#,
+        r#"{"game":{"answer":"HELLO","guesses":["HELLO"],"played":"2024-01-15T09:30:00+08:00"}}"#,
+    ]);
+
+    // the same history in an SQLite database
+    let database = "target/29_history_store.db";
+    let _ = fs::remove_file(database);
+    let stats = play_two_games(&format!("sqlite:{}", database));
+    assert_eq!(stats.trim(), "2 0 1.50\nCRANE 1 HELLO 1 SLATE 1");
+    let achievements = run(&["stats", "achievements", "-S", &format!("sqlite:{}", database)], "");
+    assert!(achievements.contains("hole_in_one UNLOCKED"));
+
+    // an explicit json: scheme is the plain state file
+    let file = "target/29_history_store.json";
+    let _ = fs::remove_file(file);
+    play_two_games(&format!("json:{}", file));
+    let stats = run(&["stats", "show", "-S", file], "");
+    assert_eq!(stats.trim(), "2 0 1.50\nCRANE 1 HELLO 1 SLATE 1");
+}